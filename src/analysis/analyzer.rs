@@ -3,55 +3,61 @@
     mem,
 };
 
-use camino::Utf8Path;
 use color_eyre::eyre::{Result, bail};
+use tracing::warn;
 use winget_types::{
     installer::Installer,
     locale::{Copyright, PackageName, Publisher},
 };
 
-use super::extensions::{APPX, APPX_BUNDLE, EXE, MSI, MSIX, MSIX_BUNDLE, ZIP};
 use crate::analysis::{
-    Installers,
+    FileType, Installers,
     installers::{
         Exe, Msi, Zip,
         msix_family::{Msix, bundle::MsixBundle},
+        pe::Authenticode,
     },
 };
 
-pub struct Analyzer<'reader, R: Read + Seek> {
+pub struct Analyzer<R: Read + Seek> {
     pub file_name: String,
     pub copyright: Option<Copyright>,
     pub package_name: Option<PackageName>,
     pub publisher: Option<Publisher>,
+    /// The installer framework or package format that the file was detected as.
+    pub framework: Option<&'static str>,
     pub installers: Vec<Installer>,
-    pub zip: Option<Zip<&'reader mut R>>,
+    pub authenticode: Option<Authenticode>,
+    pub zip: Option<Zip<R>>,
 }
 
-impl<'reader, R: Read + Seek> Analyzer<'reader, R> {
-    pub fn new(reader: &'reader mut R, file_name: &str) -> Result<Self> {
-        let extension = Utf8Path::new(file_name)
-            .extension()
-            .unwrap_or_default()
-            .to_ascii_lowercase();
+impl<R: Read + Seek> Analyzer<R> {
+    /// Analyzes an installer.
+    ///
+    /// The reader is only kept if the installer is a zip, so that its nested installers can be
+    /// chosen afterwards. Pass a mutable reference to keep ownership of the reader.
+    pub fn new(mut reader: R, file_name: &str) -> Result<Self> {
+        let file_type = Self::file_type(&mut reader, file_name)?;
 
-        let installers = match extension.as_str() {
-            MSI => Msi::new(reader)?.installers(),
-            MSIX | APPX => Msix::new(reader)?.installers(),
-            MSIX_BUNDLE | APPX_BUNDLE => MsixBundle::new(reader)?.installers(),
-            ZIP => {
+        let installers = match file_type {
+            FileType::Msi => Msi::new(&mut reader)?.installers(),
+            FileType::Msix => Msix::new(&mut reader)?.installers(),
+            FileType::MsixBundle => MsixBundle::new(&mut reader)?.installers(),
+            FileType::Zip => {
                 let mut scoped_zip = Zip::new(reader)?;
                 let installers = mem::take(&mut scoped_zip.installers);
                 return Ok(Self {
                     installers,
+                    framework: Some(file_type.as_str()),
                     zip: Some(scoped_zip),
                     ..Self::default()
                 });
             }
-            EXE => {
-                let mut exe = Exe::new(reader)?;
+            FileType::Exe => {
+                let mut exe = Exe::new(&mut reader)?;
                 return Ok(Self {
                     installers: exe.installers(),
+                    framework: Some(exe.framework()),
                     copyright: exe
                         .legal_copyright
                         .take()
@@ -63,32 +69,63 @@ pub fn new(reader: &'reader mut R, file_name: &str) -> Result<Self> {
                     publisher: exe
                         .company_name
                         .take()
-                        .and_then(|company_name| Publisher::new(company_name).ok()),
+                        .and_then(|company_name| Publisher::new(company_name).ok())
+                        .or_else(|| {
+                            // Fall back to the organization that signed the executable
+                            exe.authenticode
+                                .as_ref()?
+                                .signer
+                                .organization
+                                .clone()
+                                .and_then(|organization| Publisher::new(organization).ok())
+                        }),
+                    authenticode: exe.authenticode.take(),
                     ..Self::default()
                 });
             }
-            _ => bail!(r#"Unsupported file extension: "{extension}""#),
         };
         Ok(Self {
             installers,
+            framework: Some(file_type.as_str()),
             ..Self::default()
         })
     }
 
+    /// Determines the type of the file from its contents, falling back to its extension if the
+    /// contents are not recognized.
+    ///
+    /// A warning is emitted if the extension of the file name disagrees with its contents.
+    fn file_type(reader: &mut R, file_name: &str) -> Result<FileType> {
+        let from_extension = FileType::from_file_name(file_name);
+
+        match (FileType::detect(reader)?, from_extension) {
+            (Some(detected), Some(from_extension)) if detected != from_extension => {
+                warn!(
+                    "The extension of {file_name} indicates {from_extension} but its contents were detected as {detected}. Analyzing it as {detected}"
+                );
+                Ok(detected)
+            }
+            (Some(file_type), _) | (None, Some(file_type)) => Ok(file_type),
+            (None, None) => bail!(r#"Unsupported file type: "{file_name}""#),
+        }
+    }
+
     /// Consumes the [`Analyzer`], returning the inner installers.
     pub fn into_installers(self) -> Vec<Installer> {
         self.installers
     }
 }
 
-impl<R: Read + Seek> Default for Analyzer<'_, R> {
+impl<R: Read + Seek> Default for Analyzer<R> {
     fn default() -> Self {
         Self {
             file_name: String::default(),
             copyright: None,
             package_name: None,
             publisher: None,
+            framework: None,
             installers: Vec::default(),
+            authenticode: None,
             zip: None,
         }
     }
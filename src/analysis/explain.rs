@@ -0,0 +1,111 @@
+use std::{cell::RefCell, fmt};
+
+use serde::Serialize;
+
+pub const ARCHITECTURE: &str = "Architecture";
+pub const INSTALLER_TYPE: &str = "InstallerType";
+pub const SCOPE: &str = "Scope";
+
+thread_local! {
+    static EXPLANATIONS: RefCell<Option<Vec<Explanation>>> = const { RefCell::new(None) };
+}
+
+/// Why an analyzer chose the value of a manifest field.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Explanation {
+    /// The name of the manifest field, such as `Architecture`.
+    pub field: &'static str,
+
+    /// The value that was chosen.
+    pub value: String,
+
+    /// The parser that chose the value, such as `MSI` or `NSIS`.
+    pub source: &'static str,
+
+    /// What the value was derived from, or which fallback was taken.
+    pub reason: String,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} ({}: {})",
+            self.field, self.value, self.source, self.reason
+        )
+    }
+}
+
+/// Records why a field was given a value.
+///
+/// This does nothing unless it is called within [`record`], so the explanation is only formatted
+/// when it is asked for.
+pub fn explain(
+    field: &'static str,
+    value: impl fmt::Display,
+    source: &'static str,
+    reason: fmt::Arguments,
+) {
+    EXPLANATIONS.with_borrow_mut(|explanations| {
+        if let Some(explanations) = explanations {
+            explanations.push(Explanation {
+                field,
+                value: value.to_string(),
+                source,
+                reason: reason.to_string(),
+            });
+        }
+    });
+}
+
+/// Runs the analysis in `f`, collecting every [`explain`] call made on this thread in the order
+/// they were made.
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Explanation>) {
+    let previous = EXPLANATIONS.replace(Some(Vec::new()));
+    let result = f();
+    let explanations = EXPLANATIONS.replace(previous).unwrap_or_default();
+    (result, explanations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Explanation, explain, record};
+
+    #[test]
+    fn records_only_when_recording() {
+        explain("Scope", "user", "Test", format_args!("ignored"));
+
+        let ((), explanations) = record(|| {
+            explain(
+                "Scope",
+                "machine",
+                "Test",
+                format_args!("ALLUSERS is {}", 1),
+            );
+        });
+
+        assert_eq!(
+            explanations,
+            [Explanation {
+                field: "Scope",
+                value: "machine".to_owned(),
+                source: "Test",
+                reason: "ALLUSERS is 1".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn nested_recording() {
+        let (inner, outer) = record(|| {
+            explain("Architecture", "x64", "Outer", format_args!("outer"));
+            record(|| explain("Architecture", "x86", "Inner", format_args!("inner"))).1
+        });
+
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].source, "Inner");
+        assert_eq!(outer.len(), 1);
+        assert_eq!(outer[0].source, "Outer");
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    fmt, io,
+    io::{Read, Seek, SeekFrom},
+};
+
+use camino::Utf8Path;
+use zip::ZipArchive;
+
+use super::extensions::{APPX, APPX_BUNDLE, EXE, MSI, MSIX, MSIX_BUNDLE, ZIP};
+use crate::analysis::installers::msix_family::{
+    APPX_MANIFEST_XML, bundle::APPX_BUNDLE_MANIFEST_PATH,
+};
+
+/// The kind of file an installer is, which determines the parser used to analyze it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FileType {
+    Msi,
+    Msix,
+    MsixBundle,
+    Zip,
+    Exe,
+}
+
+impl FileType {
+    /// The signature of a Compound File Binary, which MSI databases are stored as.
+    ///
+    /// See <https://learn.microsoft.com/openspecs/windows_protocols/ms-cfb/05060311-bfce-4b12-874d-71fd4ce63aea>.
+    const CFB_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+    /// The signature of a local file header in a ZIP archive.
+    const ZIP_SIGNATURE: [u8; 4] = *b"PK\x03\x04";
+
+    /// The signature of the end of central directory record of an empty ZIP archive.
+    const EMPTY_ZIP_SIGNATURE: [u8; 4] = *b"PK\x05\x06";
+
+    /// The signature of a DOS header, which every PE file starts with.
+    const MZ_SIGNATURE: [u8; 2] = *b"MZ";
+
+    /// Returns the file type associated with the extension of a file name, if any.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = Utf8Path::new(file_name).extension()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            MSI => Some(Self::Msi),
+            MSIX | APPX => Some(Self::Msix),
+            MSIX_BUNDLE | APPX_BUNDLE => Some(Self::MsixBundle),
+            ZIP => Some(Self::Zip),
+            EXE => Some(Self::Exe),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the bytes start with the signature of any supported file type.
+    pub fn has_signature(bytes: &[u8]) -> bool {
+        [
+            Self::CFB_SIGNATURE.as_slice(),
+            &Self::ZIP_SIGNATURE,
+            &Self::EMPTY_ZIP_SIGNATURE,
+            &Self::MZ_SIGNATURE,
+        ]
+        .into_iter()
+        .any(|signature| bytes.starts_with(signature))
+    }
+
+    /// Detects the file type from the magic bytes at the start of the reader.
+    ///
+    /// ZIP archives are further inspected for an `AppxManifest.xml` or
+    /// `AppxMetadata/AppxBundleManifest.xml` to distinguish MSIX packages and bundles from plain
+    /// archives.
+    ///
+    /// The reader is rewound to the start before returning.
+    pub fn detect<R: Read + Seek>(mut reader: R) -> io::Result<Option<Self>> {
+        let mut magic = [0; Self::CFB_SIGNATURE.len()];
+
+        reader.seek(SeekFrom::Start(0))?;
+        let read = read_up_to(&mut reader, &mut magic)?;
+        let magic = &magic[..read];
+
+        let file_type = if magic.starts_with(&Self::CFB_SIGNATURE) {
+            Some(Self::Msi)
+        } else if magic.starts_with(&Self::ZIP_SIGNATURE)
+            || magic.starts_with(&Self::EMPTY_ZIP_SIGNATURE)
+        {
+            reader.seek(SeekFrom::Start(0))?;
+            Some(Self::detect_zip(&mut reader))
+        } else if magic.starts_with(&Self::MZ_SIGNATURE) {
+            Some(Self::Exe)
+        } else {
+            None
+        };
+
+        reader.seek(SeekFrom::Start(0))?;
+
+        Ok(file_type)
+    }
+
+    /// Distinguishes between an MSIX package, an MSIX bundle, and a plain ZIP archive.
+    fn detect_zip<R: Read + Seek>(reader: R) -> Self {
+        let Ok(zip) = ZipArchive::new(reader) else {
+            return Self::Zip;
+        };
+
+        if zip
+            .file_names()
+            .any(|name| name.eq_ignore_ascii_case(APPX_BUNDLE_MANIFEST_PATH))
+        {
+            Self::MsixBundle
+        } else if zip
+            .file_names()
+            .any(|name| name.eq_ignore_ascii_case(APPX_MANIFEST_XML))
+        {
+            Self::Msix
+        } else {
+            Self::Zip
+        }
+    }
+
+    /// Returns the file type as a static string.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Msi => "MSI",
+            Self::Msix => "MSIX",
+            Self::MsixBundle => "MSIX bundle",
+            Self::Zip => "ZIP",
+            Self::Exe => "EXE",
+        }
+    }
+}
+
+impl fmt::Display for FileType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// Reads as many bytes as possible into `buf`, stopping early only at EOF.
+fn read_up_to<R: Read>(mut reader: R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut total = 0;
+
+    while total < buf.len() {
+        match reader.read(&mut buf[total..]) {
+            Ok(0) => break,
+            Ok(read) => total += read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use rstest::rstest;
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::FileType;
+
+    fn zip_with(file_name: &str) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(file_name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"<Package />").unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[rstest]
+    #[case::msi("setup.msi", Some(FileType::Msi))]
+    #[case::msix("App.msix", Some(FileType::Msix))]
+    #[case::appx("App.APPX", Some(FileType::Msix))]
+    #[case::msix_bundle("App.msixbundle", Some(FileType::MsixBundle))]
+    #[case::appx_bundle("App.appxbundle", Some(FileType::MsixBundle))]
+    #[case::zip("portable.zip", Some(FileType::Zip))]
+    #[case::exe("setup.exe", Some(FileType::Exe))]
+    #[case::unknown("setup.tar.gz", None)]
+    #[case::uuid("0b3c4bd5-9b0e-4f3a-9d64-23e8b8c6ae5c", None)]
+    fn from_file_name(#[case] file_name: &str, #[case] expected: Option<FileType>) {
+        assert_eq!(FileType::from_file_name(file_name), expected);
+    }
+
+    #[rstest]
+    #[case::cfb(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1, 0x00], Some(FileType::Msi))]
+    #[case::mz(b"MZ\x90\x00\x03", Some(FileType::Exe))]
+    #[case::html(b"<!DOCTYPE html>", None)]
+    #[case::empty(b"", None)]
+    fn detect(#[case] bytes: &[u8], #[case] expected: Option<FileType>) {
+        let mut cursor = Cursor::new(bytes);
+        assert_eq!(FileType::detect(&mut cursor).unwrap(), expected);
+        assert_eq!(cursor.position(), 0);
+    }
+
+    #[rstest]
+    #[case::zip("bin/tool.exe", FileType::Zip)]
+    #[case::msix("AppxManifest.xml", FileType::Msix)]
+    #[case::msix_bundle("AppxMetadata/AppxBundleManifest.xml", FileType::MsixBundle)]
+    fn detect_zip(#[case] file_name: &str, #[case] expected: FileType) {
+        let bytes = zip_with(file_name);
+        assert_eq!(
+            FileType::detect(Cursor::new(bytes)).unwrap(),
+            Some(expected)
+        );
+    }
+}
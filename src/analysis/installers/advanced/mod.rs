@@ -18,7 +18,13 @@
 use zerocopy::IntoBytes;
 
 use super::msi::Msi;
-use crate::{analysis::Installers, read::ReadBytesExt};
+use crate::{
+    analysis::{
+        Installers,
+        explain::{INSTALLER_TYPE, explain},
+    },
+    read::ReadBytesExt,
+};
 
 #[derive(Error, Debug)]
 pub enum AdvancedInstallerError {
@@ -97,6 +103,16 @@ pub fn new<R: Read + Seek>(mut reader: R) -> Result<Self, AdvancedInstallerError
             return Err(AdvancedInstallerError::NotAdvancedInstallerFile);
         }
 
+        explain(
+            INSTALLER_TYPE,
+            InstallerType::Exe,
+            "Advanced Installer",
+            format_args!(
+                "the file has an Advanced Installer footer and wraps {} MSI package(s)",
+                installers.len()
+            ),
+        );
+
         Ok(Self { installers })
     }
 }
@@ -26,6 +26,7 @@
 use crate::{
     analysis::{
         Installers,
+        explain::{ARCHITECTURE, SCOPE, explain},
         installers::{
             burn::manifest::WixBundleScope,
             pe::{
@@ -127,12 +128,30 @@ pub fn new<R: Read + Seek>(mut reader: R, pe: &PE) -> Result<Self, BurnError> {
         let manifest = from_str::<BurnManifest>(&manifest)?;
         debug!("{manifest:#?}");
 
+        let architecture = if manifest.win_64 {
+            explain(
+                ARCHITECTURE,
+                Architecture::X64,
+                "Burn",
+                format_args!("the bundle manifest sets Win64"),
+            );
+            Architecture::X64
+        } else {
+            let architecture = Architecture::from_machine(pe.machine());
+            explain(
+                ARCHITECTURE,
+                architecture,
+                "Burn",
+                format_args!(
+                    "the bundle manifest does not set Win64, and the machine type is {:#06X}",
+                    pe.machine()
+                ),
+            );
+            architecture
+        };
+
         Ok(Self {
-            architecture: if manifest.win_64 {
-                Architecture::X64
-            } else {
-                Architecture::from_machine(pe.machine())
-            },
+            architecture,
             manifest: Some(manifest),
             msi: None,
         })
@@ -238,7 +257,18 @@ fn installers(&self) -> Vec<Installer> {
                 WixBundleScope::Machine => Some(Scope::Machine),
                 WixBundleScope::User => Some(Scope::User),
                 WixBundleScope::MachineOrUser | WixBundleScope::UserOrMachine => None,
-            },
+            }
+            .inspect(|&scope| {
+                explain(
+                    SCOPE,
+                    scope,
+                    "Burn",
+                    format_args!(
+                        "the bundle registration scope is {:?}",
+                        manifest.registration.scope()
+                    ),
+                );
+            }),
             apps_and_features_entries,
             installation_metadata: manifest
                 .variables
@@ -2,16 +2,22 @@
 
 use color_eyre::Result;
 use inno::{Inno, InnoInner, error::InnoError};
+use tracing::debug;
 use winget_types::installer::{Installer, InstallerType};
 
-use super::{super::Installers, AdvancedInstaller, Burn, Nsis, Squirrel};
+use super::{super::Installers, AdvancedInstaller, Burn, InstallShield, Nsis, Sfx, Squirrel};
 use crate::{
-    analysis::installers::{
-        advanced::AdvancedInstallerError,
-        burn::BurnError,
-        nsis::NsisError,
-        pe::{PE, VSVersionInfo},
-        squirrel::SquirrelError,
+    analysis::{
+        explain::{ARCHITECTURE, INSTALLER_TYPE, explain},
+        installers::{
+            advanced::AdvancedInstallerError,
+            burn::BurnError,
+            install_shield::InstallShieldError,
+            nsis::NsisError,
+            pe::{Authenticode, PE, VSVersionInfo},
+            sfx::SfxError,
+            squirrel::SquirrelError,
+        },
     },
     traits::IntoWingetArchitecture,
 };
@@ -25,13 +31,16 @@ pub struct Exe {
     pub legal_copyright: Option<String>,
     pub product_name: Option<String>,
     pub company_name: Option<String>,
+    pub authenticode: Option<Authenticode>,
 }
 
 pub enum ExeType {
     AdvancedInstaller(AdvancedInstaller),
     Burn(Box<Burn>),
     Inno(Box<InnoInner>),
+    InstallShield(Box<InstallShield>),
     Nsis(Nsis),
+    Sfx(Box<Sfx>),
     Squirrel(Squirrel),
     Generic(Box<Installer>),
 }
@@ -58,6 +67,15 @@ pub fn new<R: Read + Seek>(mut reader: R) -> Result<Self> {
             .and_then(|table| table.swap_remove("CompanyName"))
             .map(str::to_owned);
 
+        let authenticode = Authenticode::read_from(&mut reader, &pe)
+            .inspect_err(|error| debug!(%error, "Failed to read Authenticode signature"))
+            .ok()
+            .flatten();
+
+        if let Some(authenticode) = &authenticode {
+            debug!(?authenticode);
+        }
+
         match AdvancedInstaller::new(&mut reader) {
             Ok(advanced) => {
                 return Ok(Self {
@@ -65,6 +83,7 @@ pub fn new<R: Read + Seek>(mut reader: R) -> Result<Self> {
                     legal_copyright,
                     product_name,
                     company_name,
+                    authenticode,
                 });
             }
             Err(AdvancedInstallerError::NotAdvancedInstallerFile) => {}
@@ -78,6 +97,7 @@ pub fn new<R: Read + Seek>(mut reader: R) -> Result<Self> {
                     legal_copyright,
                     product_name,
                     company_name,
+                    authenticode,
                 });
             }
             Err(BurnError::NotBurnFile) => {}
@@ -91,6 +111,7 @@ pub fn new<R: Read + Seek>(mut reader: R) -> Result<Self> {
                     legal_copyright,
                     product_name,
                     company_name,
+                    authenticode,
                 });
             }
             Err(InnoError::NotInnoFile) => {}
@@ -104,6 +125,7 @@ pub fn new<R: Read + Seek>(mut reader: R) -> Result<Self> {
                     legal_copyright,
                     product_name,
                     company_name,
+                    authenticode,
                 });
             }
             Err(NsisError::NotNsisFile) => {}
@@ -117,36 +139,105 @@ pub fn new<R: Read + Seek>(mut reader: R) -> Result<Self> {
                     legal_copyright,
                     product_name,
                     company_name,
+                    authenticode,
                 });
             }
             Err(SquirrelError::NotSquirrelFile) => {}
             Err(error) => return Err(error.into()),
         }
 
+        match InstallShield::new(&mut reader, &pe) {
+            Ok(install_shield) => {
+                return Ok(Self {
+                    r#type: ExeType::InstallShield(Box::new(install_shield)),
+                    legal_copyright,
+                    product_name,
+                    company_name,
+                    authenticode,
+                });
+            }
+            Err(InstallShieldError::NotInstallShieldFile) => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        match Sfx::new(&mut reader, &pe) {
+            Ok(sfx) => {
+                return Ok(Self {
+                    r#type: ExeType::Sfx(Box::new(sfx)),
+                    legal_copyright,
+                    product_name,
+                    company_name,
+                    authenticode,
+                });
+            }
+            Err(SfxError::NotSfxFile) => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        let architecture = pe.winget_architecture();
+        explain(
+            ARCHITECTURE,
+            architecture,
+            "PE",
+            format_args!("the machine type is {:#06X}", pe.machine()),
+        );
+
+        let installer_type = if string_table.is_some_and(|mut table| {
+            let original_filename = table.swap_remove(ORIGINAL_FILENAME);
+            let file_description = table.swap_remove(FILE_DESCRIPTION);
+
+            BASIC_INSTALLER_KEYWORDS.iter().any(|keyword| {
+                original_filename.is_some_and(|filename| filename.contains(keyword))
+                    || file_description.is_some_and(|description| description.contains(keyword))
+            })
+        }) {
+            explain(
+                INSTALLER_TYPE,
+                InstallerType::Exe,
+                "PE",
+                format_args!(
+                    "the OriginalFilename or FileDescription version info contains an installer keyword"
+                ),
+            );
+            InstallerType::Exe
+        } else {
+            explain(
+                INSTALLER_TYPE,
+                InstallerType::Portable,
+                "PE",
+                format_args!(
+                    "no known installer framework was found and the version info does not mention an installer"
+                ),
+            );
+            InstallerType::Portable
+        };
+
         Ok(Self {
             r#type: ExeType::Generic(Box::new(Installer {
-                architecture: pe.winget_architecture(),
-                r#type: if string_table.is_some_and(|mut table| {
-                    let original_filename = table.swap_remove(ORIGINAL_FILENAME);
-                    let file_description = table.swap_remove(FILE_DESCRIPTION);
-
-                    BASIC_INSTALLER_KEYWORDS.iter().any(|keyword| {
-                        original_filename.is_some_and(|filename| filename.contains(keyword))
-                            || file_description
-                                .is_some_and(|description| description.contains(keyword))
-                    })
-                }) {
-                    Some(InstallerType::Exe)
-                } else {
-                    Some(InstallerType::Portable)
-                },
+                architecture,
+                r#type: Some(installer_type),
                 ..Installer::default()
             })),
             legal_copyright,
             product_name,
             company_name,
+            authenticode,
         })
     }
+
+    /// Returns the name of the installer framework that the executable was built with.
+    pub const fn framework(&self) -> &'static str {
+        match self.r#type {
+            ExeType::AdvancedInstaller(_) => "Advanced Installer",
+            ExeType::Burn(_) => "WiX Burn",
+            ExeType::Inno(_) => "Inno Setup",
+            ExeType::InstallShield(_) => "InstallShield",
+            ExeType::Nsis(_) => "NSIS",
+            ExeType::Sfx(_) => "Self-extracting archive",
+            ExeType::Squirrel(_) => "Squirrel",
+            ExeType::Generic(_) => "EXE",
+        }
+    }
 }
 
 impl Installers for Exe {
@@ -155,7 +246,9 @@ fn installers(&self) -> Vec<Installer> {
             ExeType::AdvancedInstaller(advanced) => advanced.installers(),
             ExeType::Burn(burn) => burn.installers(),
             ExeType::Inno(inno) => inno.installers(),
+            ExeType::InstallShield(install_shield) => install_shield.installers(),
             ExeType::Nsis(nsis) => nsis.installers(),
+            ExeType::Sfx(sfx) => sfx.installers(),
             ExeType::Squirrel(squirrel) => squirrel.installers(),
             ExeType::Generic(installer) => vec![*installer.clone()],
         }
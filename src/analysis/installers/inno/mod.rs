@@ -19,13 +19,36 @@
     RELATIVE_PROGRAM_DATA, RELATIVE_PROGRAM_FILES_32, RELATIVE_PROGRAM_FILES_64,
     RELATIVE_SYSTEM_DRIVE, RELATIVE_SYSTEM_ROOT, RELATIVE_WINDOWS_DIR,
 };
-use crate::analysis::Installers;
+use crate::analysis::{
+    Installers,
+    explain::{ARCHITECTURE, SCOPE, explain},
+};
 
 const CODE: &str = "{code:";
 
 impl Installers for InnoInner {
     fn installers(&self) -> Vec<Installer> {
         let scope = self.header().privileges_required().to_scope();
+        explain(
+            SCOPE,
+            scope,
+            "Inno",
+            format_args!(
+                "PrivilegesRequired is {:?}",
+                self.header().privileges_required()
+            ),
+        );
+
+        let architecture = WingetArchitecture::from_inno(self.header().architectures_allowed());
+        explain(
+            ARCHITECTURE,
+            architecture,
+            "Inno",
+            format_args!(
+                "ArchitecturesAllowed is {:?}",
+                self.header().architectures_allowed()
+            ),
+        );
 
         let install_dir = self
             .header()
@@ -62,7 +85,7 @@ fn installers(&self) -> Vec<Installer> {
                     .parse::<LanguageTag>()
                     .ok()
             }),
-            architecture: WingetArchitecture::from_inno(self.header().architectures_allowed()),
+            architecture,
             r#type: Some(InstallerType::Inno),
             scope: Some(scope),
             url: DecodedUrl::default(),
@@ -0,0 +1,119 @@
+use std::{io, io::Read};
+
+/// A reader that decrypts files embedded in an InstallShield setup stream.
+///
+/// Each byte has its nibbles swapped, is XORed with a key derived from the file's name, and is
+/// then inverted. Depending on the file's flags, the key position either runs continuously across
+/// the whole file or restarts at the beginning of every 1024-byte block.
+///
+/// See <https://github.com/lifenjoiner/ISx/blob/master/ISx.cpp>.
+pub struct Decoder<R> {
+    reader: R,
+    key: Vec<u8>,
+    block_size: Option<usize>,
+    position: usize,
+}
+
+impl<R: Read> Decoder<R> {
+    const MAGIC: [u8; 4] = [0x13, 0x35, 0x86, 0x07];
+
+    const BLOCK_SIZE: usize = 1024;
+
+    /// Creates a new [`Decoder`] for a file with the given name.
+    ///
+    /// If `is_blocked` is `true`, the key restarts every 1024 bytes. An empty file name leaves the
+    /// data unchanged.
+    pub fn new(reader: R, file_name: &[u8], is_blocked: bool) -> Self {
+        Self {
+            reader,
+            key: Self::key(file_name),
+            block_size: is_blocked.then_some(Self::BLOCK_SIZE),
+            position: 0,
+        }
+    }
+
+    fn key(file_name: &[u8]) -> Vec<u8> {
+        file_name
+            .iter()
+            .zip(Self::MAGIC.iter().cycle())
+            .map(|(byte, magic)| byte ^ magic)
+            .collect()
+    }
+
+    #[inline]
+    const fn decode_byte(byte: u8, key: u8) -> u8 {
+        !(key ^ byte.rotate_left(4))
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+
+        if !self.key.is_empty() {
+            for byte in &mut buf[..read] {
+                let position = self
+                    .block_size
+                    .map_or(self.position, |block_size| self.position % block_size);
+                *byte = Self::decode_byte(*byte, self.key[position % self.key.len()]);
+                self.position += 1;
+            }
+        }
+
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use rstest::rstest;
+
+    use super::Decoder;
+
+    fn encode(data: &[u8], file_name: &[u8], block_size: Option<usize>) -> Vec<u8> {
+        let key = Decoder::<&[u8]>::key(file_name);
+        data.iter()
+            .enumerate()
+            .map(|(index, byte)| {
+                let position = block_size.map_or(index, |block_size| index % block_size);
+                (!byte ^ key[position % key.len()]).rotate_right(4)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn known_answer() {
+        // The first bytes of an encrypted `setup.msi`, which decode to the signature that every
+        // MSI starts with as an OLE compound file
+        const ENCODED: [u8; 8] = [0xF4, 0x06, 0xC1, 0xD6, 0xD3, 0x55, 0xE0, 0xA6];
+        const COMPOUND_FILE_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+        let mut decoded = Vec::new();
+        Decoder::new(ENCODED.as_slice(), b"setup.msi", false)
+            .read_to_end(&mut decoded)
+            .unwrap();
+
+        assert_eq!(decoded, COMPOUND_FILE_SIGNATURE);
+    }
+
+    #[rstest]
+    #[case::continuous(false, None)]
+    #[case::blocked(true, Some(1024))]
+    fn round_trip(#[case] is_blocked: bool, #[case] block_size: Option<usize>) {
+        const FILE_NAME: &[u8] = b"setup.msi";
+
+        let data = (0..4096)
+            .map(|index| (index % 251) as u8)
+            .collect::<Vec<_>>();
+        let encoded = encode(&data, FILE_NAME, block_size);
+
+        let mut decoded = Vec::new();
+        Decoder::new(encoded.as_slice(), FILE_NAME, is_blocked)
+            .read_to_end(&mut decoded)
+            .unwrap();
+
+        assert_eq!(decoded, data);
+    }
+}
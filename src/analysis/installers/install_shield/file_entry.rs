@@ -0,0 +1,136 @@
+use std::fmt;
+
+use zerocopy::{FromBytes, Immutable, KnownLayout, LE, U16, U32, Unaligned};
+
+/// A file entry in an `InstallShield` setup stream, where the file name is stored inline as a
+/// null-terminated ANSI string.
+#[derive(Clone, Copy, FromBytes, KnownLayout, Immutable, Unaligned)]
+#[repr(C)]
+pub struct FileEntry {
+    file_name: [u8; 260],
+
+    encoded_flags: U32<LE>,
+
+    x3: U32<LE>,
+
+    /// The file's size in bytes.
+    file_len: U32<LE>,
+
+    x5: [u8; 8],
+
+    is_unicode_launcher: U16<LE>,
+
+    x7: [u8; 30],
+}
+
+impl FileEntry {
+    /// Returns the file name up to its null terminator.
+    pub fn file_name(&self) -> &[u8] {
+        let end = self
+            .file_name
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(self.file_name.len());
+
+        &self.file_name[..end]
+    }
+
+    #[inline]
+    pub const fn encoded_flags(&self) -> u32 {
+        self.encoded_flags.get()
+    }
+
+    /// Returns the file's size in bytes.
+    #[inline]
+    pub const fn file_len(&self) -> u32 {
+        self.file_len.get()
+    }
+
+    #[inline]
+    pub const fn is_unicode_launcher(&self) -> bool {
+        self.is_unicode_launcher.get() != 0
+    }
+}
+
+impl fmt::Debug for FileEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileEntry")
+            .field("file_name", &String::from_utf8_lossy(self.file_name()))
+            .field("encoded_flags", &self.encoded_flags())
+            .field("file_len", &self.file_len())
+            .field("is_unicode_launcher", &self.is_unicode_launcher())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A file entry in an `ISSetupStream` setup stream, which is followed by a UTF-16LE file name of
+/// [`name_len`] bytes.
+///
+/// [`name_len`]: StreamFileEntry::name_len
+#[derive(Clone, Copy, FromBytes, KnownLayout, Immutable, Unaligned)]
+#[repr(C)]
+pub struct StreamFileEntry {
+    /// The size of the file name in bytes.
+    name_len: U32<LE>,
+
+    encoded_flags: U32<LE>,
+
+    x3: [u8; 2],
+
+    /// The file's size in bytes.
+    file_len: U32<LE>,
+
+    x5: [u8; 8],
+
+    is_unicode_launcher: U16<LE>,
+}
+
+impl StreamFileEntry {
+    /// Returns the size of the file name that follows this entry in bytes.
+    #[inline]
+    pub const fn name_len(&self) -> u32 {
+        self.name_len.get()
+    }
+
+    #[inline]
+    pub const fn encoded_flags(&self) -> u32 {
+        self.encoded_flags.get()
+    }
+
+    /// Returns the file's size in bytes.
+    #[inline]
+    pub const fn file_len(&self) -> u32 {
+        self.file_len.get()
+    }
+
+    #[inline]
+    pub const fn is_unicode_launcher(&self) -> bool {
+        self.is_unicode_launcher.get() != 0
+    }
+}
+
+impl fmt::Debug for StreamFileEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamFileEntry")
+            .field("name_len", &self.name_len())
+            .field("encoded_flags", &self.encoded_flags())
+            .field("file_len", &self.file_len())
+            .field("is_unicode_launcher", &self.is_unicode_launcher())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileEntry, StreamFileEntry};
+
+    #[test]
+    fn file_entry_size() {
+        assert_eq!(size_of::<FileEntry>(), 312);
+    }
+
+    #[test]
+    fn stream_file_entry_size() {
+        assert_eq!(size_of::<StreamFileEntry>(), 24);
+    }
+}
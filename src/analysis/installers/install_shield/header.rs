@@ -0,0 +1,87 @@
+use std::fmt;
+
+use zerocopy::{FromBytes, Immutable, KnownLayout, LE, U16, U32, Unaligned};
+
+/// The header of the InstallShield setup stream appended to the overlay of a `setup.exe`.
+///
+/// Sources:
+/// * <https://github.com/lifenjoiner/ISx/blob/master/ISx.h>
+/// * <https://github.com/SabreTools/SabreTools.Serialization/blob/main/SabreTools.Data.Models/InstallShieldExecutable/FileEntry.cs>
+#[derive(Clone, Copy, FromBytes, KnownLayout, Immutable, Unaligned)]
+#[repr(C)]
+pub struct Header {
+    /// Either `InstallShield\0` or `ISSetupStream\0`.
+    signature: [u8; 14],
+
+    num_files: U16<LE>,
+
+    r#type: U32<LE>,
+
+    x4: [u8; 8],
+
+    x5: U16<LE>,
+
+    x6: [u8; 16],
+}
+
+impl Header {
+    /// The signature of setup streams with fixed-size ANSI file names.
+    pub const INSTALL_SHIELD: &[u8; 14] = b"InstallShield\0";
+
+    /// The signature of setup streams with variable-length UTF-16 file names.
+    pub const IS_SETUP_STREAM: &[u8; 14] = b"ISSetupStream\0";
+
+    /// Returns `true` if the header has a known InstallShield signature.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.is_install_shield() || self.is_setup_stream()
+    }
+
+    /// Returns `true` if the file entries have fixed-size ANSI file names.
+    #[inline]
+    pub fn is_install_shield(&self) -> bool {
+        &self.signature == Self::INSTALL_SHIELD
+    }
+
+    /// Returns `true` if the file entries have variable-length UTF-16 file names.
+    #[inline]
+    pub fn is_setup_stream(&self) -> bool {
+        &self.signature == Self::IS_SETUP_STREAM
+    }
+
+    /// Returns the number of files in the setup stream.
+    #[inline]
+    pub const fn num_files(&self) -> u16 {
+        self.num_files.get()
+    }
+
+    #[inline]
+    pub const fn r#type(&self) -> u32 {
+        self.r#type.get()
+    }
+}
+
+impl fmt::Debug for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Header")
+            .field("signature", &String::from_utf8_lossy(&self.signature))
+            .field("num_files", &self.num_files())
+            .field("type", &self.r#type())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Header;
+
+    #[test]
+    fn size() {
+        assert_eq!(size_of::<Header>(), 46);
+    }
+
+    #[test]
+    fn alignment() {
+        assert_eq!(align_of::<Header>(), 1);
+    }
+}
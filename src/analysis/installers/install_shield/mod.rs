@@ -0,0 +1,223 @@
+mod decoder;
+mod file_entry;
+mod header;
+
+use std::{
+    io,
+    io::{Read, Seek, SeekFrom},
+};
+
+use camino::Utf8Path;
+use decoder::Decoder;
+use encoding_rs::UTF_16LE;
+use file_entry::{FileEntry, StreamFileEntry};
+use header::Header;
+use thiserror::Error;
+use tracing::debug;
+use winget_types::installer::{Architecture, Installer, InstallerType, Switches};
+
+use super::msi::Msi;
+use crate::{
+    analysis::{
+        Installers,
+        explain::{ARCHITECTURE, INSTALLER_TYPE, explain},
+        installers::pe::PE,
+    },
+    read::ReadBytesExt,
+    traits::FromMachine,
+};
+
+#[derive(Error, Debug)]
+pub enum InstallShieldError {
+    #[error("File is not an InstallShield installer")]
+    NotInstallShieldFile,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+pub struct InstallShield {
+    architecture: Architecture,
+    msi: Option<Msi>,
+}
+
+impl InstallShield {
+    pub fn new<R: Read + Seek>(mut reader: R, pe: &PE) -> Result<Self, InstallShieldError> {
+        let overlay_offset = pe
+            .overlay_offset()
+            .ok_or(InstallShieldError::NotInstallShieldFile)?;
+
+        reader.seek(SeekFrom::Start(overlay_offset))?;
+        let header = reader
+            .read_t::<Header>()
+            .map_err(|_| InstallShieldError::NotInstallShieldFile)?;
+
+        if !header.is_valid() {
+            return Err(InstallShieldError::NotInstallShieldFile);
+        }
+
+        debug!(?header);
+
+        // A corrupt file count or truncated file table means this is not a setup stream that can
+        // be read, so the executable is analyzed as a generic one instead
+        let files = Self::files(&mut reader, &header).map_err(|error| {
+            debug!(%error, "Failed to read the InstallShield file table");
+            InstallShieldError::NotInstallShieldFile
+        })?;
+
+        let msi = files.iter().filter(|file| file.is_msi()).find_map(|file| {
+            let mut msi_file = tempfile::tempfile().ok()?;
+            io::copy(&mut file.reader(&mut reader).ok()?, &mut msi_file).ok()?;
+            msi_file.rewind().ok()?;
+            Msi::new(msi_file)
+                .inspect_err(|error| debug!(file = %file.name, %error))
+                .ok()
+        });
+
+        explain(
+            INSTALLER_TYPE,
+            InstallerType::Exe,
+            "InstallShield",
+            format_args!("the overlay starts with an InstallShield setup stream"),
+        );
+
+        let architecture = if let Some(msi) = &msi {
+            explain(
+                ARCHITECTURE,
+                msi.architecture,
+                "InstallShield",
+                format_args!("the setup stream contains an MSI"),
+            );
+            msi.architecture
+        } else {
+            let architecture = Architecture::from_machine(pe.machine());
+            explain(
+                ARCHITECTURE,
+                architecture,
+                "InstallShield",
+                format_args!(
+                    "the setup stream has no MSI, and the machine type of the setup is {:#06X}",
+                    pe.machine()
+                ),
+            );
+            architecture
+        };
+
+        Ok(Self { architecture, msi })
+    }
+
+    /// Reads the file entries that follow the header, skipping over each file's contents.
+    fn files<R: Read + Seek>(mut reader: R, header: &Header) -> io::Result<Vec<SetupFile>> {
+        let mut files = Vec::with_capacity(header.num_files().into());
+        for _ in 0..header.num_files() {
+            let file = if header.is_setup_stream() {
+                let entry = reader.read_t::<StreamFileEntry>()?;
+                debug!(?entry);
+
+                let mut name = vec![0; entry.name_len() as usize];
+                reader.read_exact(&mut name)?;
+
+                SetupFile {
+                    name: UTF_16LE
+                        .decode_without_bom_handling(&name)
+                        .0
+                        .trim_end_matches('\0')
+                        .to_owned(),
+                    encoded_flags: entry.encoded_flags(),
+                    offset: reader.stream_position()?,
+                    size: entry.file_len(),
+                }
+            } else {
+                let entry = reader.read_t::<FileEntry>()?;
+                debug!(?entry);
+
+                SetupFile {
+                    name: String::from_utf8_lossy(entry.file_name()).into_owned(),
+                    encoded_flags: entry.encoded_flags(),
+                    offset: reader.stream_position()?,
+                    size: entry.file_len(),
+                }
+            };
+
+            debug!(?file);
+            reader.seek(SeekFrom::Current(file.size.into()))?;
+            files.push(file);
+        }
+
+        Ok(files)
+    }
+}
+
+impl Installers for InstallShield {
+    fn installers(&self) -> Vec<Installer> {
+        let Some(msi) = &self.msi else {
+            // InstallScript setups can only be installed silently with a recorded response file
+            return vec![Installer {
+                architecture: self.architecture,
+                r#type: Some(InstallerType::Exe),
+                ..Installer::default()
+            }];
+        };
+
+        msi.installers()
+            .into_iter()
+            .map(|installer| Installer {
+                r#type: Some(InstallerType::Exe),
+                // https://docs.revenera.com/installshield/helplibrary/IHelpSetup_EXECmdLine.htm
+                switches: Switches::builder()
+                    .silent(r#"/s /v"/qn""#.parse().unwrap())
+                    .silent_with_progress(r#"/s /v"/qb""#.parse().unwrap())
+                    .install_location(r#"/v"INSTALLDIR=\"<INSTALLPATH>\"""#.parse().unwrap())
+                    .log(r#"/v"/l*v \"<LOGPATH>\"""#.parse().unwrap())
+                    .build(),
+                ..installer
+            })
+            .collect()
+    }
+}
+
+/// A file embedded in an InstallShield setup stream.
+#[derive(Debug)]
+struct SetupFile {
+    name: String,
+    encoded_flags: u32,
+    offset: u64,
+    size: u32,
+}
+
+impl SetupFile {
+    /// Returns `true` if the file is encrypted.
+    #[inline]
+    const fn is_encrypted(&self) -> bool {
+        self.encoded_flags & 0b110 != 0
+    }
+
+    /// Returns `true` if the encryption key restarts at every 1024-byte block.
+    #[inline]
+    const fn is_blocked(&self) -> bool {
+        self.encoded_flags & 0b100 != 0
+    }
+
+    /// Returns `true` if the file is an `msi` file.
+    fn is_msi(&self) -> bool {
+        Utf8Path::new(&self.name.replace('\\', "/"))
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("msi"))
+    }
+
+    /// Returns a reader over the decrypted contents of the file.
+    fn reader<R: Read + Seek>(&self, mut reader: R) -> io::Result<impl Read> {
+        reader.seek(SeekFrom::Start(self.offset))?;
+
+        let reader = reader.take(self.size.into());
+
+        Ok(if self.is_encrypted() {
+            let file_name = Utf8Path::new(&self.name.replace('\\', "/"))
+                .file_name()
+                .unwrap_or(&self.name)
+                .to_owned();
+            Decoder::new(reader, file_name.as_bytes(), self.is_blocked())
+        } else {
+            Decoder::new(reader, &[], false)
+        })
+    }
+}
@@ -2,10 +2,12 @@
 pub mod burn;
 mod exe;
 pub mod inno;
+mod install_shield;
 mod msi;
 pub mod msix_family;
 pub mod nsis;
 pub mod pe;
+mod sfx;
 pub mod squirrel;
 pub mod utils;
 mod zip;
@@ -13,7 +15,9 @@
 pub use advanced::AdvancedInstaller;
 pub use burn::Burn;
 pub use exe::Exe;
+pub use install_shield::InstallShield;
 pub use msi::Msi;
 pub use nsis::Nsis;
+pub use sfx::Sfx;
 pub use squirrel::Squirrel;
 pub use zip::Zip;
@@ -0,0 +1,137 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    io,
+    io::{Read, Seek},
+};
+
+use msi::{Package, Select};
+use tracing::debug;
+use winget_types::installer::FileExtension;
+
+/// Represents the [Windows Installer Extension Table](https://learn.microsoft.com/windows/win32/msi/extension-table),
+/// joined with the [ProgId](https://learn.microsoft.com/windows/win32/msi/progid-table) and
+/// [Verb](https://learn.microsoft.com/windows/win32/msi/verb-table) tables that link each
+/// extension to the program that opens it.
+#[derive(Clone, Debug, Default)]
+pub struct ExtensionTable(Vec<ExtensionEntry>);
+
+#[derive(Clone, Debug)]
+pub struct ExtensionEntry {
+    pub extension: String,
+    /// The ProgId that the extension is associated with, if it exists in the ProgId table.
+    pub prog_id: Option<String>,
+    /// The verbs, such as `open`, that are registered for the extension.
+    pub verbs: Vec<String>,
+}
+
+impl ExtensionTable {
+    const EXTENSION: &str = "Extension";
+    const PROG_ID: &str = "ProgId";
+    const VERB: &str = "Verb";
+
+    /// Reads the `Extension` table from the given MSI package, along with the ProgIds and verbs
+    /// that each extension is linked to.
+    ///
+    /// Packages without file associations usually have none of these tables, in which case an
+    /// empty [`ExtensionTable`] is returned.
+    pub fn new<R: Read + Seek>(msi: &mut Package<R>) -> io::Result<Self> {
+        const EXTENSION_FOREIGN_KEY: &str = "Extension_";
+        const PROG_ID_FOREIGN_KEY: &str = "ProgId_";
+
+        if !msi.has_table(Self::EXTENSION) {
+            return Ok(Self::default());
+        }
+
+        let prog_ids = if msi.has_table(Self::PROG_ID) {
+            msi.select_rows(Select::table(Self::PROG_ID).columns(&[Self::PROG_ID]))?
+                .filter_map(|row| row[0].as_str().map(str::to_owned))
+                .collect::<BTreeSet<_>>()
+        } else {
+            BTreeSet::new()
+        };
+
+        let mut verbs = HashMap::<String, Vec<String>>::new();
+        if msi.has_table(Self::VERB) {
+            for row in msi.select_rows(
+                Select::table(Self::VERB).columns(&[EXTENSION_FOREIGN_KEY, Self::VERB]),
+            )? {
+                if let (Some(extension), Some(verb)) = (row[0].as_str(), row[1].as_str()) {
+                    verbs
+                        .entry(extension.to_owned())
+                        .or_default()
+                        .push(verb.to_owned());
+                }
+            }
+        }
+
+        Ok(Self(
+            msi.select_rows(Select::table(Self::EXTENSION))?
+                .filter_map(|row| {
+                    let extension = row[Self::EXTENSION].as_str()?.to_owned();
+                    Some(ExtensionEntry {
+                        prog_id: row[PROG_ID_FOREIGN_KEY]
+                            .as_str()
+                            .filter(|prog_id| prog_ids.contains(*prog_id))
+                            .map(str::to_owned),
+                        verbs: verbs.get(&extension).cloned().unwrap_or_default(),
+                        extension,
+                    })
+                })
+                .inspect(|entry| debug!(?entry))
+                .collect(),
+        ))
+    }
+
+    /// Returns an iterator over the file extensions that the package opens.
+    ///
+    /// An extension without a ProgId or verb only registers its content type, so it is not
+    /// associated with the package.
+    pub fn file_extensions(&self) -> impl Iterator<Item = FileExtension> {
+        self.0
+            .iter()
+            .filter(|entry| entry.prog_id.is_some() || !entry.verbs.is_empty())
+            .filter_map(|entry| FileExtension::new(entry.extension.to_ascii_lowercase()).ok())
+    }
+}
+
+impl FromIterator<ExtensionEntry> for ExtensionTable {
+    fn from_iter<T: IntoIterator<Item = ExtensionEntry>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use winget_types::installer::FileExtension;
+
+    use super::{ExtensionEntry, ExtensionTable};
+
+    fn entry(extension: &str, prog_id: Option<&str>, verbs: &[&str]) -> ExtensionEntry {
+        ExtensionEntry {
+            extension: extension.to_owned(),
+            prog_id: prog_id.map(str::to_owned),
+            verbs: verbs.iter().copied().map(str::to_owned).collect(),
+        }
+    }
+
+    #[test]
+    fn file_extensions() {
+        let extension_table = [
+            entry("EXAMPLE", Some("Example.Document"), &[]),
+            entry("ex2", None, &["open", "print"]),
+            entry("mime", None, &[]),
+        ]
+        .into_iter()
+        .collect::<ExtensionTable>();
+
+        assert_eq!(
+            extension_table.file_extensions().collect::<BTreeSet<_>>(),
+            BTreeSet::from([
+                FileExtension::new("example").unwrap(),
+                FileExtension::new("ex2").unwrap(),
+            ])
+        );
+    }
+}
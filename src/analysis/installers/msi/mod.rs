@@ -1,24 +1,33 @@
 mod directory_table;
+mod extension_table;
 mod property_table;
+mod registry_table;
 
 use std::{
+    collections::BTreeSet,
     io::{Error, ErrorKind, Read, Result, Seek},
     str::SplitAsciiWhitespace,
 };
 
 use camino::Utf8PathBuf;
+use extension_table::ExtensionTable;
 use msi::{Language, Package, Select};
 use property_table::PropertyTable;
+use registry_table::RegistryTable;
 use winget_types::{
     LanguageTag,
     installer::{
-        AppsAndFeaturesEntries, AppsAndFeaturesEntry, Architecture, InstallationMetadata,
-        Installer, InstallerType, Scope, Switches,
+        AppsAndFeaturesEntries, AppsAndFeaturesEntry, Architecture, FileExtension,
+        InstallationMetadata, Installer, InstallerType, Protocol, Scope, Switches,
     },
 };
 
 use crate::{
-    analysis::{installers::msi::directory_table::DirectoryTable, r#trait::Installers},
+    analysis::{
+        explain::{ARCHITECTURE, SCOPE, explain},
+        installers::msi::directory_table::DirectoryTable,
+        r#trait::Installers,
+    },
     traits::{AsciiExt, path::NormalizePath},
 };
 
@@ -35,6 +44,8 @@ pub struct Msi {
     pub may_mutate_all_users: bool,
     pub property_table: PropertyTable,
     pub directory_table: DirectoryTable,
+    pub file_extensions: BTreeSet<FileExtension>,
+    pub protocols: BTreeSet<Protocol>,
     pub creating_application: Option<String>,
     pub comments: Option<String>,
 }
@@ -55,6 +66,20 @@ pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
                 ));
             }
         };
+        match msi.summary_info().arch() {
+            Some(arch) => explain(
+                ARCHITECTURE,
+                architecture,
+                "MSI",
+                format_args!(r#"the platform in the Template summary property is "{arch}""#),
+            ),
+            None => explain(
+                ARCHITECTURE,
+                architecture,
+                "MSI",
+                format_args!("the Template summary property has no platform, so x86 is assumed"),
+            ),
+        }
 
         let property_table = PropertyTable::new(&mut msi)?;
 
@@ -63,6 +88,18 @@ pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
             .is_ok_and(|mut rows| rows.any(|row| row[0].as_str() == Some(ALL_USERS)));
 
         let directory_table = DirectoryTable::new(&mut msi)?;
+
+        let registry_table = RegistryTable::new(&mut msi)?;
+
+        // Extensions registered through the Extension table, which are complemented by any file
+        // associations written directly to the registry
+        let mut file_extensions = ExtensionTable::new(&mut msi)?
+            .file_extensions()
+            .collect::<BTreeSet<_>>();
+        file_extensions.extend(registry_table.file_extensions());
+
+        let protocols = registry_table.protocols().collect();
+
         let summary_info = msi.summary_info();
 
         Ok(Self {
@@ -70,6 +107,8 @@ pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
             may_mutate_all_users,
             property_table,
             directory_table,
+            file_extensions,
+            protocols,
             creating_application: summary_info.creating_application().map(str::to_owned),
             comments: summary_info.comments().map(str::to_owned),
         })
@@ -84,19 +123,45 @@ fn build_directory(&self, current_dir: &str, target_dir: &str) -> Option<Utf8Pat
     pub fn find_scope(&self) -> Option<Scope> {
         // https://learn.microsoft.com/windows/win32/msi/allusers
         match self.property_table.get(ALL_USERS) {
-            Some("1") => Some(Scope::Machine),
+            Some("1") => {
+                explain(SCOPE, Scope::Machine, "MSI", format_args!("ALLUSERS is 1"));
+                Some(Scope::Machine)
+            }
             Some("2") => None, // Installs depending on installation context and user privileges
-            Some("") => Some(Scope::User), // An empty string specifies per-user context
+            Some("") => {
+                // An empty string specifies per-user context
+                explain(SCOPE, Scope::User, "MSI", format_args!("ALLUSERS is empty"));
+                Some(Scope::User)
+            }
             _ => {
                 if self.may_mutate_all_users {
                     // ALLUSERS could be changed at runtime
                     None
                 } else {
                     // No value or control specifies per-user context
-                    self.find_install_directory()
+                    let install_directory = self.find_install_directory();
+                    let scope = install_directory
                         .as_deref()
-                        .and_then(Scope::from_install_directory)
-                        .or(Some(Scope::User))
+                        .and_then(Scope::from_install_directory);
+                    match (scope, install_directory) {
+                        (Some(scope), Some(install_directory)) => explain(
+                            SCOPE,
+                            scope,
+                            "MSI",
+                            format_args!(
+                                "ALLUSERS is not set and the Directory table resolves the install directory to {install_directory}"
+                            ),
+                        ),
+                        _ => explain(
+                            SCOPE,
+                            Scope::User,
+                            "MSI",
+                            format_args!(
+                                "ALLUSERS is not set and no control can change it, which specifies a per-user install"
+                            ),
+                        ),
+                    }
+                    scope.or(Some(Scope::User))
                 }
             }
         }
@@ -276,6 +341,8 @@ fn installers(&self) -> Vec<Installer> {
             scope: self.find_scope(),
             switches: self.install_location_switches(),
             product_code: product_code.map(str::to_owned),
+            file_extensions: self.file_extensions.clone(),
+            protocols: self.protocols.clone(),
             apps_and_features_entries: if product_name.is_some()
                 || manufacturer.is_some()
                 || product_version.is_some()
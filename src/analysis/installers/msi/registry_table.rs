@@ -0,0 +1,138 @@
+use std::{
+    io,
+    io::{Read, Seek},
+};
+
+use msi::{Package, Select};
+use tracing::debug;
+use winget_types::installer::{FileExtension, Protocol};
+
+use crate::analysis::installers::utils::registry::RegistryRoot;
+
+/// Represents the [Windows Installer Registry Table](https://learn.microsoft.com/windows/win32/msi/registry-table),
+/// which holds the registry values that an MSI package writes when its components are installed.
+///
+/// Only the location and name of each value is kept, which is enough to tell which file
+/// associations and URL protocols the package registers.
+#[derive(Clone, Debug, Default)]
+pub struct RegistryTable(Vec<RegistryEntry>);
+
+#[derive(Clone, Debug)]
+pub struct RegistryEntry {
+    pub root: RegistryRoot,
+    pub key: String,
+    pub name: Option<String>,
+}
+
+impl RegistryTable {
+    const REGISTRY: &str = "Registry";
+
+    /// Reads the `Registry` table from the given MSI package.
+    ///
+    /// Many packages do not write to the registry at all, in which case the table does not exist
+    /// and an empty [`RegistryTable`] is returned.
+    pub fn new<R: Read + Seek>(msi: &mut Package<R>) -> io::Result<Self> {
+        const ROOT: &str = "Root";
+        const KEY: &str = "Key";
+        const NAME: &str = "Name";
+
+        if !msi.has_table(Self::REGISTRY) {
+            return Ok(Self::default());
+        }
+
+        Ok(Self(
+            msi.select_rows(Select::table(Self::REGISTRY))?
+                .filter_map(|row| {
+                    // https://learn.microsoft.com/windows/win32/msi/registry-table#root
+                    let root = match row[ROOT].as_int()? {
+                        -1 => RegistryRoot::CurrentUserOrLocalMachine,
+                        0 => RegistryRoot::ClassesRoot,
+                        1 => RegistryRoot::CurrentUser,
+                        2 => RegistryRoot::LocalMachine,
+                        3 => RegistryRoot::Users,
+                        _ => return None,
+                    };
+
+                    Some(RegistryEntry {
+                        root,
+                        key: row[KEY].as_str()?.to_owned(),
+                        name: row[NAME].as_str().map(str::to_owned),
+                    })
+                })
+                .inspect(|entry| debug!(?entry))
+                .collect(),
+        ))
+    }
+
+    /// Returns an iterator over the file extensions that the package associates itself with.
+    pub fn file_extensions(&self) -> impl Iterator<Item = FileExtension> {
+        self.0
+            .iter()
+            .filter_map(|entry| entry.root.file_extension(&entry.key))
+    }
+
+    /// Returns an iterator over the URL protocols that the package registers a handler for.
+    pub fn protocols(&self) -> impl Iterator<Item = Protocol> {
+        self.0.iter().filter_map(|entry| {
+            entry
+                .root
+                .protocol(&entry.key, entry.name.as_deref().unwrap_or_default())
+        })
+    }
+}
+
+impl FromIterator<RegistryEntry> for RegistryTable {
+    fn from_iter<T: IntoIterator<Item = RegistryEntry>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use winget_types::installer::{FileExtension, Protocol};
+
+    use super::{RegistryEntry, RegistryTable};
+    use crate::analysis::installers::utils::registry::RegistryRoot;
+
+    fn entry(root: RegistryRoot, key: &str, name: Option<&str>) -> RegistryEntry {
+        RegistryEntry {
+            root,
+            key: key.to_owned(),
+            name: name.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn file_associations() {
+        let registry_table = [
+            entry(RegistryRoot::ClassesRoot, ".example", None),
+            entry(
+                RegistryRoot::CurrentUserOrLocalMachine,
+                r"Software\Classes\.ex2\OpenWithProgids",
+                Some("Example.Document"),
+            ),
+            entry(RegistryRoot::ClassesRoot, "example", Some("URL Protocol")),
+            entry(
+                RegistryRoot::LocalMachine,
+                r"Software\Example",
+                Some("InstallDir"),
+            ),
+        ]
+        .into_iter()
+        .collect::<RegistryTable>();
+
+        assert_eq!(
+            registry_table.file_extensions().collect::<BTreeSet<_>>(),
+            BTreeSet::from([
+                FileExtension::new("example").unwrap(),
+                FileExtension::new("ex2").unwrap(),
+            ])
+        );
+        assert_eq!(
+            registry_table.protocols().collect::<BTreeSet<_>>(),
+            BTreeSet::from([Protocol::new("example").unwrap()])
+        );
+    }
+}
@@ -20,7 +20,10 @@
     Msix,
     utils::{hash_signature, read_manifest},
 };
-use crate::analysis::Installers;
+use crate::analysis::{
+    Installers,
+    explain::{ARCHITECTURE, explain},
+};
 
 pub struct MsixBundle {
     pub signature_sha_256: Sha256String,
@@ -28,7 +31,7 @@ pub struct MsixBundle {
     pub msix_files: Vec<Msix>,
 }
 
-const APPX_BUNDLE_MANIFEST_PATH: &str = "AppxMetadata/AppxBundleManifest.xml";
+pub const APPX_BUNDLE_MANIFEST_PATH: &str = "AppxMetadata/AppxBundleManifest.xml";
 
 impl MsixBundle {
     pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
@@ -83,7 +86,14 @@ pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
                     let mut embedded_msix = zip.by_name(&file_name)?;
                     let mut temp_file = tempfile::tempfile()?;
                     io::copy(&mut embedded_msix, &mut temp_file)?;
-                    Msix::new(BufReader::new(temp_file))
+                    let msix = Msix::new(BufReader::new(temp_file))?;
+                    explain(
+                        ARCHITECTURE,
+                        msix.architecture(),
+                        "MSIX bundle",
+                        format_args!("{file_name} is an application package in the bundle"),
+                    );
+                    Ok(msix)
                 })
                 .collect::<Result<Vec<_>>>()?,
             signature_sha_256,
@@ -6,21 +6,26 @@
     io::{Read, Seek},
 };
 
+use camino::Utf8Path;
 use color_eyre::eyre::Result;
 use quick_xml::{Reader, XmlVersion, events::Event};
 use winget_types::{
     Sha256String,
     installer::{
-        AppsAndFeaturesEntry, Architecture, Capability, FileExtension, InstallationMetadata,
-        Installer, InstallerType, MinimumOSVersion, PackageFamilyName, Platform,
-        RestrictedCapability, UpgradeBehavior,
+        AppsAndFeaturesEntry, Architecture, Capability, Command, FileExtension,
+        InstallationMetadata, Installer, InstallerType, MinimumOSVersion, PackageFamilyName,
+        Platform, Protocol, RestrictedCapability, UpgradeBehavior,
     },
 };
 use zip::ZipArchive;
 
 use super::msix_family::utils::{get_install_location, hash_signature, read_manifest};
 use crate::{
-    analysis::{Installers, extensions::MSIX},
+    analysis::{
+        Installers,
+        explain::{ARCHITECTURE, INSTALLER_TYPE, explain},
+        extensions::MSIX,
+    },
     traits::AsciiExt,
 };
 
@@ -30,7 +35,7 @@ pub struct Msix {
     pub manifest: Package,
 }
 
-const APPX_MANIFEST_XML: &str = "AppxManifest.xml";
+pub const APPX_MANIFEST_XML: &str = "AppxManifest.xml";
 
 const MSIX_MIN_VERSION: MinimumOSVersion = MinimumOSVersion::new(10, 0, 17763, 0);
 
@@ -42,9 +47,137 @@ pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
 
         let signature_sha_256 = hash_signature(&mut zip)?;
 
+        let manifest = Package::from_manifest(&appx_manifest)?;
+
+        Ok(Self {
+            appx_manifest,
+            signature_sha_256,
+            manifest,
+        })
+    }
+
+    /// Returns the architecture of the package's identity, which is x86 if it is not given.
+    fn architecture(&self) -> Architecture {
+        self.manifest
+            .identity
+            .processor_architecture
+            .parse()
+            .unwrap_or(Architecture::X86)
+    }
+}
+
+impl Installers for Msix {
+    fn installers(&self) -> Vec<Installer> {
+        let is_appx = self
+            .manifest
+            .dependencies
+            .target_device_family
+            .iter()
+            .all(|target_device_family| target_device_family.min_version < MSIX_MIN_VERSION)
+            && !self.appx_manifest.contains_ignore_ascii_case(MSIX);
+        let installer_type = if is_appx {
+            explain(
+                INSTALLER_TYPE,
+                InstallerType::Appx,
+                "MSIX",
+                format_args!(
+                    "every target device family is older than Windows 10 1809 and the manifest does not mention MSIX"
+                ),
+            );
+            InstallerType::Appx
+        } else {
+            explain(
+                INSTALLER_TYPE,
+                InstallerType::Msix,
+                "MSIX",
+                format_args!(
+                    "a target device family is Windows 10 1809 or newer, or the manifest mentions MSIX"
+                ),
+            );
+            InstallerType::Msix
+        };
+
+        let architecture = self.architecture();
+        explain(
+            ARCHITECTURE,
+            architecture,
+            "MSIX",
+            format_args!(
+                r#"the ProcessorArchitecture of the package identity is "{}""#,
+                self.manifest.identity.processor_architecture
+            ),
+        );
+
+        vec![Installer {
+            platform: self
+                .manifest
+                .dependencies
+                .target_device_family
+                .iter()
+                .map(|target_device_family| target_device_family.name)
+                .collect(),
+            minimum_os_version: self
+                .manifest
+                .dependencies
+                .target_device_family
+                .iter()
+                .map(|target_device_family| target_device_family.min_version)
+                .min(),
+            architecture,
+            r#type: Some(installer_type),
+            signature_sha_256: Some(self.signature_sha_256.clone()),
+            upgrade_behavior: Some(UpgradeBehavior::Install),
+            file_extensions: self
+                .manifest
+                .file_type_association
+                .supported_file_types
+                .clone(),
+            protocols: self.manifest.protocols.clone(),
+            commands: self.manifest.app_execution_alias.execution_aliases.clone(),
+            package_family_name: Some(PackageFamilyName::new(
+                &self.manifest.identity.name,
+                &self.manifest.identity.publisher,
+            )),
+            capabilities: self.manifest.capabilities.unrestricted.clone(),
+            restricted_capabilities: self.manifest.capabilities.restricted.clone(),
+            apps_and_features_entries: AppsAndFeaturesEntry::builder()
+                .display_name(&self.manifest.properties.display_name)
+                .publisher(&self.manifest.properties.publisher_display_name)
+                .display_version(&self.manifest.identity.version)
+                .build()
+                .into(),
+            installation_metadata: InstallationMetadata::new_install_location(
+                get_install_location(
+                    &self.manifest.identity.name,
+                    &self.manifest.identity.publisher,
+                    &self.manifest.identity.version,
+                    &self.manifest.identity.processor_architecture,
+                    &self.manifest.identity.resource_id,
+                ),
+            ),
+            ..Installer::default()
+        }]
+    }
+}
+
+/// <https://learn.microsoft.com/uwp/schemas/appxpackage/uapmanifestschema/element-package>
+#[derive(Clone, Default)]
+pub struct Package {
+    identity: Identity,
+    properties: Properties,
+    dependencies: Dependencies,
+    capabilities: Capabilities,
+    file_type_association: FileTypeAssociation,
+    protocols: BTreeSet<Protocol>,
+    app_execution_alias: AppExecutionAlias,
+}
+
+impl Package {
+    /// Parses the parts of an `AppxManifest.xml` that are used to fill in an installer.
+    fn from_manifest(appx_manifest: &str) -> Result<Self> {
         let mut manifest = Package::default();
 
-        let mut reader = Reader::from_str(&appx_manifest);
+        let mut reader = Reader::from_str(appx_manifest);
         let config = reader.config_mut();
         config.expand_empty_elements = true;
         config.trim_text(true);
@@ -134,6 +267,37 @@ pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
                                 .insert(extension);
                         }
                     }
+                    b"Protocol" => {
+                        if let Some(attribute) = event
+                            .attributes()
+                            .flatten()
+                            .find(|attribute| attribute.key.as_ref() == b"Name")
+                            && let Ok(protocol) =
+                                Protocol::new(attribute.normalized_value(XmlVersion::Implicit1_0)?)
+                        {
+                            manifest.protocols.insert(protocol);
+                        }
+                    }
+                    b"ExecutionAlias" => {
+                        if let Some(attribute) = event
+                            .attributes()
+                            .flatten()
+                            .find(|attribute| attribute.key.as_ref() == b"Alias")
+                        {
+                            let alias = attribute.normalized_value(XmlVersion::Implicit1_0)?;
+
+                            // Aliases are registered with their extension but are run without it
+                            if let Some(command) = Utf8Path::new(&alias)
+                                .file_stem()
+                                .and_then(|command| Command::new(command).ok())
+                            {
+                                manifest
+                                    .app_execution_alias
+                                    .execution_aliases
+                                    .insert(command);
+                            }
+                        }
+                    }
                     b"Capability" => {
                         if let Some(attribute) = event
                             .attributes()
@@ -166,93 +330,10 @@ pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
             }
         }
 
-        Ok(Self {
-            appx_manifest,
-            signature_sha_256,
-            manifest,
-        })
-    }
-}
-
-impl Installers for Msix {
-    fn installers(&self) -> Vec<Installer> {
-        let is_appx = self
-            .manifest
-            .dependencies
-            .target_device_family
-            .iter()
-            .all(|target_device_family| target_device_family.min_version < MSIX_MIN_VERSION)
-            && !self.appx_manifest.contains_ignore_ascii_case(MSIX);
-
-        vec![Installer {
-            platform: self
-                .manifest
-                .dependencies
-                .target_device_family
-                .iter()
-                .map(|target_device_family| target_device_family.name)
-                .collect(),
-            minimum_os_version: self
-                .manifest
-                .dependencies
-                .target_device_family
-                .iter()
-                .map(|target_device_family| target_device_family.min_version)
-                .min(),
-            architecture: self
-                .manifest
-                .identity
-                .processor_architecture
-                .parse()
-                .unwrap_or(Architecture::X86),
-            r#type: if is_appx {
-                Some(InstallerType::Appx)
-            } else {
-                Some(InstallerType::Msix)
-            },
-            signature_sha_256: Some(self.signature_sha_256.clone()),
-            upgrade_behavior: Some(UpgradeBehavior::Install),
-            file_extensions: self
-                .manifest
-                .file_type_association
-                .supported_file_types
-                .clone(),
-            package_family_name: Some(PackageFamilyName::new(
-                &self.manifest.identity.name,
-                &self.manifest.identity.publisher,
-            )),
-            capabilities: self.manifest.capabilities.unrestricted.clone(),
-            restricted_capabilities: self.manifest.capabilities.restricted.clone(),
-            apps_and_features_entries: AppsAndFeaturesEntry::builder()
-                .display_name(&self.manifest.properties.display_name)
-                .publisher(&self.manifest.properties.publisher_display_name)
-                .display_version(&self.manifest.identity.version)
-                .build()
-                .into(),
-            installation_metadata: InstallationMetadata::new_install_location(
-                get_install_location(
-                    &self.manifest.identity.name,
-                    &self.manifest.identity.publisher,
-                    &self.manifest.identity.version,
-                    &self.manifest.identity.processor_architecture,
-                    &self.manifest.identity.resource_id,
-                ),
-            ),
-            ..Installer::default()
-        }]
+        Ok(manifest)
     }
 }
 
-/// <https://learn.microsoft.com/uwp/schemas/appxpackage/uapmanifestschema/element-package>
-#[derive(Clone, Default)]
-pub struct Package {
-    identity: Identity,
-    properties: Properties,
-    dependencies: Dependencies,
-    capabilities: Capabilities,
-    file_type_association: FileTypeAssociation,
-}
-
 /// <https://learn.microsoft.com/uwp/schemas/appxpackage/uapmanifestschema/element-identity>
 #[derive(Clone, Default)]
 pub struct Identity {
@@ -295,3 +376,70 @@ pub struct Capabilities {
 pub struct FileTypeAssociation {
     supported_file_types: BTreeSet<FileExtension>,
 }
+
+/// <https://learn.microsoft.com/uwp/schemas/appxpackage/uapmanifestschema/element-uap3-appexecutionalias>
+#[derive(Clone, Default)]
+pub struct AppExecutionAlias {
+    execution_aliases: BTreeSet<Command>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use indoc::indoc;
+    use winget_types::installer::{Command, Protocol};
+
+    use super::Package;
+
+    #[test]
+    fn protocols_and_execution_aliases() {
+        const APPX_MANIFEST: &str = indoc! {r#"
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package
+              xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+              xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10"
+              xmlns:uap3="http://schemas.microsoft.com/appx/manifest/uap/windows10/3"
+              xmlns:uap5="http://schemas.microsoft.com/appx/manifest/uap/windows10/5"
+              xmlns:desktop="http://schemas.microsoft.com/appx/manifest/desktop/windows10"
+              IgnorableNamespaces="uap uap3 uap5 desktop">
+              <Identity Name="Example.Terminal" Publisher="CN=Example" Version="1.2.3.0" ProcessorArchitecture="x64" />
+              <Applications>
+                <Application Id="App" Executable="Terminal.exe" EntryPoint="Windows.FullTrustApplication">
+                  <Extensions>
+                    <uap:Extension Category="windows.protocol">
+                      <uap:Protocol Name="example-terminal" />
+                    </uap:Extension>
+                    <uap3:Extension Category="windows.appExecutionAlias" Executable="Terminal.exe" EntryPoint="Windows.FullTrustApplication">
+                      <uap3:AppExecutionAlias>
+                        <desktop:ExecutionAlias Alias="term.exe" />
+                        <desktop:ExecutionAlias Alias="example.exe" />
+                      </uap3:AppExecutionAlias>
+                    </uap3:Extension>
+                    <uap5:Extension Category="windows.appExecutionAlias" Executable="Terminal.exe" EntryPoint="Windows.FullTrustApplication">
+                      <uap5:AppExecutionAlias>
+                        <uap5:ExecutionAlias Alias="term.exe" />
+                      </uap5:AppExecutionAlias>
+                    </uap5:Extension>
+                  </Extensions>
+                </Application>
+              </Applications>
+            </Package>
+        "#};
+
+        let package = Package::from_manifest(APPX_MANIFEST).unwrap();
+
+        assert_eq!(package.identity.name, "Example.Terminal");
+        assert_eq!(
+            package.protocols,
+            BTreeSet::from([Protocol::new("example-terminal").unwrap()])
+        );
+        assert_eq!(
+            package.app_execution_alias.execution_aliases,
+            BTreeSet::from([
+                Command::new("example").unwrap(),
+                Command::new("term").unwrap(),
+            ])
+        );
+    }
+}
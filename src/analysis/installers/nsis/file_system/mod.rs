@@ -229,6 +229,45 @@ pub fn exists<T>(&self, path: T, location: RelativeLocation) -> bool
         false
     }
 
+    /// Returns an iterator over the files directly inside the directory at the given path, relative
+    /// to the root.
+    ///
+    /// The iterator is empty if the directory does not exist.
+    pub fn files_in<T>(&self, path: T) -> impl Iterator<Item = &FsEntry>
+    where
+        T: AsRef<Utf8WindowsPath>,
+    {
+        let mut current = Some(self.root);
+
+        for component in Self::parse_path(&path) {
+            let Some(directory) = current else {
+                break;
+            };
+
+            match component {
+                Utf8WindowsComponent::RootDir => current = Some(self.root),
+                Utf8WindowsComponent::ParentDir => {
+                    current = Some(directory.parent(&self.arena).unwrap_or(directory));
+                }
+                Utf8WindowsComponent::Normal(part) => {
+                    current = directory.children(&self.arena).find(|&id| {
+                        self.arena.get(id).is_some_and(|node| {
+                            !node.is_removed() && node.get().name().eq_ignore_ascii_case(part)
+                        })
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        current
+            .into_iter()
+            .flat_map(|directory| directory.children(&self.arena))
+            .filter_map(|id| self.arena.get(id).filter(|node| !node.is_removed()))
+            .map(Node::get)
+            .filter(|entry| entry.is_file())
+    }
+
     /// Deletes a path.
     ///
     /// If `flags` contains [`SIMPLE`], the file is deleted relative to the current directory.
@@ -12,6 +12,7 @@
 mod version;
 
 use std::{
+    collections::BTreeSet,
     io,
     io::{Read, Seek, SeekFrom},
 };
@@ -31,7 +32,7 @@
 use winget_types::{
     LanguageTag,
     installer::{
-        AppsAndFeaturesEntries, AppsAndFeaturesEntry, Architecture, InstallationMetadata,
+        AppsAndFeaturesEntries, AppsAndFeaturesEntry, Architecture, Command, InstallationMetadata,
         Installer, InstallerType, Scope,
     },
 };
@@ -48,7 +49,10 @@
     utils::{LzmaStreamHeader, RELATIVE_PROGRAM_FILES_64, RELATIVE_TEMP_FOLDER},
 };
 use crate::{
-    analysis::Installers,
+    analysis::{
+        Installers,
+        explain::{ARCHITECTURE, INSTALLER_TYPE, SCOPE, explain},
+    },
     traits::{FromMachine, IntoWingetArchitecture},
 };
 
@@ -59,6 +63,7 @@ pub struct Nsis {
     pub architecture: Architecture,
     pub is_portable: bool,
     pub registry: Registry,
+    pub commands: BTreeSet<Command>,
     pub primary_language_id: u16,
     pub install_directory: Option<Utf8WindowsPathBuf>,
 }
@@ -131,26 +136,75 @@ pub fn new<R: Read + Seek>(mut reader: R, pe: &PE) -> Result<Self, NsisError> {
         let mut architecture =
             Option::from(architecture).filter(|&architecture| architecture != Architecture::X86);
 
+        if let Some(architecture) = architecture {
+            explain(
+                ARCHITECTURE,
+                architecture,
+                "NSIS",
+                format_args!(
+                    "the installer executable's machine type is {:#06X}",
+                    pe.machine()
+                ),
+            );
+        }
+
         for entry in state.file_system.entries().map(FsEntry::name) {
             // If there is an app-64 entry, the app is x64.
             // If there is an app-32 entry or both entries are present, the app is x86.
             // (x86 apps can still install on x64 systems)
             if entry.contains(APP_64) && architecture.is_none() {
                 architecture = Some(Architecture::X64);
+                explain(
+                    ARCHITECTURE,
+                    Architecture::X64,
+                    "NSIS",
+                    format_args!(r#"the installer contains an "{entry}" entry"#),
+                );
             } else if entry.contains(APP_32) {
                 architecture = Some(Architecture::X86);
+                explain(
+                    ARCHITECTURE,
+                    Architecture::X86,
+                    "NSIS",
+                    format_args!(r#"the installer contains an "{entry}" entry"#),
+                );
             }
         }
 
         debug!(%state.registry, %state.file_system);
 
+        // Executables registered under App Paths or placed in a directory added to PATH can be run
+        // by name
+        let commands = state
+            .registry
+            .app_paths()
+            .chain(
+                state
+                    .registry
+                    .path_directories()
+                    .flat_map(|directory| state.file_system.files_in(directory))
+                    .map(|file| Utf8Path::new(file.name()))
+                    .filter(|file| {
+                        file.extension()
+                            .is_some_and(|extension| extension.eq_ignore_ascii_case(EXE))
+                    })
+                    .filter_map(|file| Command::new(file.file_stem()?).ok()),
+            )
+            .collect::<BTreeSet<_>>();
+
         architecture = architecture
             .or_else(|| {
-                state
+                let install_dir = state
                     .variables
                     .install_dir()
-                    .is_some_and(|dir| dir.as_str().contains(RELATIVE_PROGRAM_FILES_64))
-                    .then_some(Architecture::X64)
+                    .filter(|dir| dir.as_str().contains(RELATIVE_PROGRAM_FILES_64))?;
+                explain(
+                    ARCHITECTURE,
+                    Architecture::X64,
+                    "NSIS",
+                    format_args!("the install directory {install_dir} is in Program Files"),
+                );
+                Some(Architecture::X64)
             })
             .or_else(|| {
                 let app_name = state.get_string(state.language_table.name_offset()?);
@@ -203,14 +257,49 @@ pub fn new<R: Read + Seek>(mut reader: R, pe: &PE) -> Result<Self, NsisError> {
                         }
 
                         let machine = machine_from_exe_reader(decoder).ok()?;
-                        Some(Architecture::from_machine(machine))
+                        let architecture = Architecture::from_machine(machine);
+                        explain(
+                            ARCHITECTURE,
+                            architecture,
+                            "NSIS",
+                            format_args!(
+                                r#"the machine type of "{}", the executable named closest to "{app_name}", is {machine:#06X}"#,
+                                file.name()
+                            ),
+                        );
+                        Some(architecture)
                     })
             });
 
+        let is_portable = state.is_portable();
+        explain(
+            INSTALLER_TYPE,
+            if is_portable {
+                InstallerType::Portable
+            } else {
+                InstallerType::Nullsoft
+            },
+            "NSIS",
+            if is_portable {
+                format_args!("the installer sets the portable executable environment variables")
+            } else {
+                format_args!("the installer is a Nullsoft installer")
+            },
+        );
+
         Ok(Self {
-            architecture: architecture.unwrap_or(Architecture::X86),
-            is_portable: state.is_portable(),
+            architecture: architecture.unwrap_or_else(|| {
+                explain(
+                    ARCHITECTURE,
+                    Architecture::X86,
+                    "NSIS",
+                    format_args!("no other architecture was found, so x86 is assumed"),
+                );
+                Architecture::X86
+            }),
+            is_portable,
             registry: state.registry,
+            commands,
             install_directory: state
                 .variables
                 .install_dir()
@@ -247,7 +336,19 @@ fn installers(&self) -> Vec<Installer> {
             scope: self
                 .install_directory
                 .as_deref()
-                .and_then(Scope::from_install_directory),
+                .and_then(|install_directory| {
+                    Scope::from_install_directory(install_directory).inspect(|&scope| {
+                        explain(
+                            SCOPE,
+                            scope,
+                            "NSIS",
+                            format_args!("the install directory is {install_directory}"),
+                        );
+                    })
+                }),
+            commands: self.commands.clone(),
+            protocols: self.registry.protocols().collect(),
+            file_extensions: self.registry.file_extensions().collect(),
             product_code: product_code.map(str::to_owned),
             apps_and_features_entries: if display_name.is_some()
                 || publisher.is_some()
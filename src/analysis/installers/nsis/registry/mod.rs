@@ -3,9 +3,11 @@
 
 use std::{borrow::Borrow, collections::BTreeMap, fmt};
 
+use camino::Utf8Path;
 use itertools::Itertools;
 pub use root::RegRoot;
 pub use r#type::RegType;
+use winget_types::installer::{Command, FileExtension, Protocol};
 
 type Key = String;
 
@@ -37,6 +39,16 @@
 
 const CURRENT_VERSION_UNINSTALL: &str = r"Software\Microsoft\Windows\CurrentVersion\Uninstall";
 
+const APP_PATHS: &str = r"Software\Microsoft\Windows\CurrentVersion\App Paths\";
+
+/// The keys that hold the `Path` environment variable for the current user and the machine.
+const ENVIRONMENT_KEYS: [&str; 2] = [
+    "Environment",
+    r"SYSTEM\CurrentControlSet\Control\Session Manager\Environment",
+];
+
+const PATH: &str = "Path";
+
 impl Registry {
     #[inline]
     pub const fn new() -> Self {
@@ -55,6 +67,63 @@ pub fn product_code(&self) -> Option<&str> {
         })
     }
 
+    /// Returns an iterator over the file extensions that were associated by writes to class keys.
+    pub fn file_extensions(&self) -> impl Iterator<Item = FileExtension> {
+        self.0.iter().flat_map(|(root, keys)| {
+            keys.keys()
+                .filter_map(|key| root.predefined_key()?.file_extension(key))
+        })
+    }
+
+    /// Returns an iterator over the URL protocols that were registered by writes to class keys.
+    pub fn protocols(&self) -> impl Iterator<Item = Protocol> {
+        self.0.iter().flat_map(|(root, keys)| {
+            keys.iter().flat_map(move |(key, values)| {
+                values
+                    .keys()
+                    .filter_map(move |name| root.predefined_key()?.protocol(key, name))
+            })
+        })
+    }
+
+    /// Returns an iterator over the commands registered under
+    /// `Software\Microsoft\Windows\CurrentVersion\App Paths`.
+    ///
+    /// See <https://learn.microsoft.com/windows/win32/shell/app-registration#using-the-app-paths-subkey>.
+    pub fn app_paths(&self) -> impl Iterator<Item = Command> {
+        self.0.values().flat_map(|keys| {
+            keys.keys().filter_map(|key| {
+                let executable = key
+                    .get(..APP_PATHS.len())
+                    .filter(|prefix| prefix.eq_ignore_ascii_case(APP_PATHS))
+                    .map(|_| &key[APP_PATHS.len()..])?;
+
+                Command::new(Utf8Path::new(executable).file_stem()?).ok()
+            })
+        })
+    }
+
+    /// Returns an iterator over the directories that were written to the `Path` environment
+    /// variable, excluding references to other variables such as `%PATH%`.
+    pub fn path_directories(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .values()
+            .flat_map(|keys| {
+                keys.iter()
+                    .filter(|(key, _)| {
+                        ENVIRONMENT_KEYS
+                            .iter()
+                            .any(|environment| key.eq_ignore_ascii_case(environment))
+                    })
+                    .flat_map(|(_, values)| values.iter())
+            })
+            .filter(|(name, _)| name.eq_ignore_ascii_case(PATH))
+            .flat_map(|(_, value)| value.split(';'))
+            .map(|directory| directory.trim().trim_end_matches('\\'))
+            .filter(|directory| !directory.is_empty())
+            .filter(|directory| !(directory.starts_with('%') && directory.ends_with('%')))
+    }
+
     /// Inserts the value into the registry.
     ///
     /// If the registry did not have this value name present, [`None`] is returned.
@@ -151,3 +220,73 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use winget_types::installer::{Command, FileExtension, Protocol};
+
+    use super::{RegRoot, Registry};
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        registry.insert_value(
+            RegRoot::HKEY_CLASSES_ROOT,
+            ".example",
+            "",
+            "Example.Document",
+        );
+        registry.insert_value(
+            RegRoot::SHELL_CONTEXT,
+            r"Software\Classes\example",
+            "URL Protocol",
+            "",
+        );
+        registry.insert_value(
+            RegRoot::HKEY_LOCAL_MACHINE64,
+            r"Software\Microsoft\Windows\CurrentVersion\App Paths\example.exe",
+            "",
+            r"C:\Program Files\Example\example.exe",
+        );
+        registry.insert_value(
+            RegRoot::HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Environment",
+            "Path",
+            r"%PATH%;C:\Program Files\Example\bin\",
+        );
+        registry
+    }
+
+    #[test]
+    fn file_extensions() {
+        assert_eq!(
+            registry().file_extensions().collect::<BTreeSet<_>>(),
+            BTreeSet::from([FileExtension::new("example").unwrap()])
+        );
+    }
+
+    #[test]
+    fn protocols() {
+        assert_eq!(
+            registry().protocols().collect::<BTreeSet<_>>(),
+            BTreeSet::from([Protocol::new("example").unwrap()])
+        );
+    }
+
+    #[test]
+    fn app_paths() {
+        assert_eq!(
+            registry().app_paths().collect::<BTreeSet<_>>(),
+            BTreeSet::from([Command::new("example").unwrap()])
+        );
+    }
+
+    #[test]
+    fn path_directories() {
+        assert_eq!(
+            registry().path_directories().collect::<Vec<_>>(),
+            [r"C:\Program Files\Example\bin"]
+        );
+    }
+}
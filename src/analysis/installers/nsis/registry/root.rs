@@ -2,6 +2,8 @@
 
 use zerocopy::{FromBytes, Immutable, KnownLayout, LE, U32};
 
+use crate::analysis::installers::utils::registry::RegistryRoot;
+
 #[derive(Copy, Clone, Hash, PartialEq, Eq, FromBytes, KnownLayout, Immutable)]
 pub struct RegRoot(U32<LE>);
 
@@ -61,6 +63,21 @@ const fn get(self) -> u32 {
         self.0.get()
     }
 
+    /// Returns the predefined key that this root refers to, regardless of any forced registry view.
+    ///
+    /// `SHELL_CONTEXT` refers to `HKEY_CURRENT_USER` or `HKEY_LOCAL_MACHINE` depending on
+    /// `SetShellVarContext`.
+    pub const fn predefined_key(self) -> Option<RegistryRoot> {
+        match Self::new(self.get() & !Self::REG_ROOT_VIEW_ANY) {
+            Self::SHELL_CONTEXT => Some(RegistryRoot::CurrentUserOrLocalMachine),
+            Self::HKEY_CLASSES_ROOT => Some(RegistryRoot::ClassesRoot),
+            Self::HKEY_CURRENT_USER => Some(RegistryRoot::CurrentUser),
+            Self::HKEY_LOCAL_MACHINE => Some(RegistryRoot::LocalMachine),
+            Self::HKEY_USERS => Some(RegistryRoot::Users),
+            _ => None,
+        }
+    }
+
     /// Returns the registry root as a static string slice if it's known, or `None` otherwise.
     const fn as_str(self) -> Option<&'static str> {
         match self {
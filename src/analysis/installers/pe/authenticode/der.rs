@@ -0,0 +1,258 @@
+use std::io;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use encoding_rs::UTF_16BE;
+
+pub const INTEGER: u8 = 0x02;
+pub const OCTET_STRING: u8 = 0x04;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const UTF8_STRING: u8 = 0x0C;
+pub const PRINTABLE_STRING: u8 = 0x13;
+pub const T61_STRING: u8 = 0x14;
+pub const IA5_STRING: u8 = 0x16;
+pub const UTC_TIME: u8 = 0x17;
+pub const GENERALIZED_TIME: u8 = 0x18;
+pub const BMP_STRING: u8 = 0x1E;
+pub const SEQUENCE: u8 = 0x30;
+pub const SET: u8 = 0x31;
+
+/// Returns the tag of a constructed, context-specific element, such as `[0]`.
+pub const fn context(number: u8) -> u8 {
+    0xA0 | number
+}
+
+/// A single DER-encoded tag-length-value element.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Element<'data> {
+    tag: u8,
+    contents: &'data [u8],
+    raw: &'data [u8],
+}
+
+impl<'data> Element<'data> {
+    /// Returns the identifier octet of the element.
+    #[inline]
+    pub const fn tag(self) -> u8 {
+        self.tag
+    }
+
+    /// Returns the contents of the element, without its tag and length.
+    #[inline]
+    pub const fn contents(self) -> &'data [u8] {
+        self.contents
+    }
+
+    /// Returns the full encoding of the element, including its tag and length.
+    #[inline]
+    pub const fn raw(self) -> &'data [u8] {
+        self.raw
+    }
+
+    /// Returns a reader over the children of a constructed element.
+    #[inline]
+    pub const fn reader(self) -> DerReader<'data> {
+        DerReader::new(self.contents)
+    }
+
+    /// Decodes the element as one of the string types used in X.501 names.
+    pub fn as_string(self) -> Option<String> {
+        match self.tag {
+            UTF8_STRING | PRINTABLE_STRING | IA5_STRING => {
+                str::from_utf8(self.contents).ok().map(str::to_owned)
+            }
+            // T61String is in practice used to hold Latin-1
+            T61_STRING => Some(self.contents.iter().copied().map(char::from).collect()),
+            BMP_STRING => Some(
+                UTF_16BE
+                    .decode_without_bom_handling(self.contents)
+                    .0
+                    .into_owned(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Decodes the element as a `UTCTime` or `GeneralizedTime`.
+    pub fn as_time(self) -> Option<DateTime<Utc>> {
+        let time = str::from_utf8(self.contents).ok()?.strip_suffix('Z')?;
+
+        let time = match self.tag {
+            UTC_TIME => {
+                // Two-digit years of 50 or greater are in the 20th century, as per RFC 5280
+                let year = time.get(..2)?.parse::<u8>().ok()?;
+                let century = if year >= 50 { "19" } else { "20" };
+                NaiveDateTime::parse_from_str(&format!("{century}{time}"), "%Y%m%d%H%M%S").ok()?
+            }
+            GENERALIZED_TIME => NaiveDateTime::parse_from_str(time, "%Y%m%d%H%M%S%.f").ok()?,
+            _ => return None,
+        };
+
+        Some(time.and_utc())
+    }
+}
+
+/// A minimal reader for the subset of DER needed to walk an Authenticode signature.
+///
+/// Only definite lengths are supported, as indefinite lengths are not permitted in DER.
+#[derive(Copy, Clone, Debug)]
+pub struct DerReader<'data> {
+    data: &'data [u8],
+}
+
+impl<'data> DerReader<'data> {
+    #[inline]
+    pub const fn new(data: &'data [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns `true` if there are no more elements to read.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Reads the next element.
+    pub fn read(&mut self) -> io::Result<Element<'data>> {
+        let [tag, first_length, rest @ ..] = self.data else {
+            return Err(invalid_data("Unexpected end of DER data"));
+        };
+
+        let (length, rest) = if first_length & 0x80 == 0 {
+            (usize::from(*first_length), rest)
+        } else {
+            let num_octets = usize::from(first_length & 0x7F);
+            if num_octets == 0 || num_octets > size_of::<u32>() || rest.len() < num_octets {
+                return Err(invalid_data("Invalid DER length"));
+            }
+
+            let (length_octets, rest) = rest.split_at(num_octets);
+            let length = length_octets
+                .iter()
+                .fold(0, |length, &octet| (length << 8) | usize::from(octet));
+
+            (length, rest)
+        };
+
+        if rest.len() < length {
+            return Err(invalid_data("DER element is longer than its container"));
+        }
+
+        let header_length = self.data.len() - rest.len();
+        let (raw, remaining) = self.data.split_at(header_length + length);
+        self.data = remaining;
+
+        Ok(Element {
+            tag: *tag,
+            contents: &raw[header_length..],
+            raw,
+        })
+    }
+
+    /// Reads the next element, returning an error if it does not have the expected tag.
+    pub fn expect(&mut self, tag: u8) -> io::Result<Element<'data>> {
+        let element = self.read()?;
+
+        if element.tag == tag {
+            Ok(element)
+        } else {
+            Err(invalid_data(format!(
+                "Expected DER tag {tag:#04X} but found {:#04X}",
+                element.tag
+            )))
+        }
+    }
+
+    /// Reads the next element only if it has the given tag.
+    pub fn optional(&mut self, tag: u8) -> io::Result<Option<Element<'data>>> {
+        if self.data.first() == Some(&tag) {
+            self.read().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'data> Iterator for DerReader<'data> {
+    type Item = io::Result<Element<'data>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_empty() {
+            None
+        } else {
+            let element = self.read();
+            if element.is_err() {
+                // Stop iterating after an error rather than repeatedly returning it
+                self.data = &[];
+            }
+            Some(element)
+        }
+    }
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use rstest::rstest;
+
+    use super::{DerReader, GENERALIZED_TIME, OCTET_STRING, SEQUENCE, UTC_TIME};
+
+    #[test]
+    fn short_length() {
+        let mut reader = DerReader::new(&[SEQUENCE, 0x03, OCTET_STRING, 0x01, 0xFF]);
+        let sequence = reader.expect(SEQUENCE).unwrap();
+        assert!(reader.is_empty());
+
+        let octet_string = sequence.reader().expect(OCTET_STRING).unwrap();
+        assert_eq!(octet_string.contents(), [0xFF]);
+        assert_eq!(octet_string.raw(), [OCTET_STRING, 0x01, 0xFF]);
+    }
+
+    #[test]
+    fn long_length() {
+        let mut data = vec![OCTET_STRING, 0x82, 0x01, 0x00];
+        data.extend([0xAB; 0x100]);
+
+        let element = DerReader::new(&data).expect(OCTET_STRING).unwrap();
+        assert_eq!(element.contents().len(), 0x100);
+    }
+
+    #[rstest]
+    #[case::truncated(&[OCTET_STRING, 0x05, 0x00])]
+    #[case::indefinite(&[SEQUENCE, 0x80, 0x00, 0x00])]
+    #[case::empty(&[])]
+    fn invalid(#[case] data: &[u8]) {
+        assert!(DerReader::new(data).read().is_err());
+    }
+
+    #[test]
+    fn optional() {
+        let mut reader = DerReader::new(&[OCTET_STRING, 0x00]);
+        assert!(reader.optional(SEQUENCE).unwrap().is_none());
+        assert!(reader.optional(OCTET_STRING).unwrap().is_some());
+    }
+
+    #[rstest]
+    #[case::utc_time(UTC_TIME, "240315120000Z", 2024)]
+    #[case::utc_time_20th_century(UTC_TIME, "990315120000Z", 1999)]
+    #[case::generalized_time(GENERALIZED_TIME, "20240315120000Z", 2024)]
+    #[case::generalized_time_fraction(GENERALIZED_TIME, "20240315120000.123Z", 2024)]
+    fn as_time(#[case] tag: u8, #[case] time: &str, #[case] year: i32) {
+        let mut data = vec![tag, time.len() as u8];
+        data.extend(time.as_bytes());
+
+        let time = DerReader::new(&data).read().unwrap().as_time().unwrap();
+        assert_eq!(
+            time.date_naive(),
+            Utc.with_ymd_and_hms(year, 3, 15, 12, 0, 0)
+                .unwrap()
+                .date_naive()
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// A hash algorithm that an Authenticode signature can use to digest the PE image.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    const MD5: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x02, 0x05];
+    const SHA1: &[u8] = &[0x2B, 0x0E, 0x03, 0x02, 0x1A];
+    const SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+    const SHA384: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02];
+    const SHA512: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03];
+
+    /// Returns the digest algorithm identified by a DER-encoded object identifier.
+    pub fn from_oid(oid: &[u8]) -> Option<Self> {
+        match oid {
+            Self::MD5 => Some(Self::Md5),
+            Self::SHA1 => Some(Self::Sha1),
+            Self::SHA256 => Some(Self::Sha256),
+            Self::SHA384 => Some(Self::Sha384),
+            Self::SHA512 => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the digest algorithm as a static string.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+        }
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
@@ -0,0 +1,511 @@
+mod der;
+mod digest_algorithm;
+mod signer_info;
+mod win_certificate;
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use chrono::{DateTime, Utc};
+use der::{DerReader, Element, INTEGER, OBJECT_IDENTIFIER, OCTET_STRING, SEQUENCE, SET, context};
+pub use digest_algorithm::DigestAlgorithm;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use signer_info::SignerInfo;
+use tracing::debug;
+pub use win_certificate::WinCertificate;
+
+use super::{PE, optional_header::StandardFields};
+use crate::read::ReadBytesExt;
+
+/// The Authenticode signature embedded in the certificate table of a PE file.
+///
+/// See <https://learn.microsoft.com/windows-hardware/drivers/install/authenticode>.
+#[derive(Clone, Debug)]
+pub struct Authenticode {
+    /// The subject of the certificate that made the signature.
+    pub signer: Signer,
+
+    /// The time the file was signed, if the signature was timestamped.
+    pub signing_time: Option<DateTime<Utc>>,
+
+    /// The algorithm used to digest the PE image, if it is a known one.
+    pub digest_algorithm: Option<DigestAlgorithm>,
+
+    digest: Vec<u8>,
+}
+
+/// The subject of a signer's certificate.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Signer {
+    pub common_name: Option<String>,
+    pub organization: Option<String>,
+}
+
+/// `1.2.840.113549.1.7.2`
+const SIGNED_DATA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02];
+
+/// `1.3.6.1.4.1.311.2.1.4`
+const SPC_INDIRECT_DATA: &[u8] = &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x02, 0x01, 0x04];
+
+/// `2.5.4.3`
+const COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+
+/// `2.5.4.10`
+const ORGANIZATION_NAME: &[u8] = &[0x55, 0x04, 0x0A];
+
+impl Authenticode {
+    /// Reads the first Authenticode signature from the certificate table of a PE file.
+    ///
+    /// Returns `None` if the file is not signed.
+    pub fn read_from<R: Read + Seek>(mut reader: R, pe: &PE) -> io::Result<Option<Self>> {
+        let Some(certificate_table) = pe
+            .certificate_table()
+            .filter(|table| table.virtual_address() != 0 && table.size() != 0)
+        else {
+            return Ok(None);
+        };
+
+        // Unlike other data directories, the certificate table's address is a file offset
+        reader.seek(SeekFrom::Start(certificate_table.virtual_address().into()))?;
+        let win_certificate = reader.read_t::<WinCertificate>()?;
+
+        debug!(?win_certificate);
+
+        if !win_certificate.is_pkcs_signed_data() {
+            return Ok(None);
+        }
+
+        let data_length = win_certificate.data_length().min(certificate_table.size()) as usize;
+        let mut data = vec![0; data_length];
+        reader.read_exact(&mut data)?;
+
+        Self::from_der(&data).map(Some)
+    }
+
+    /// Parses a DER-encoded PKCS #7 `ContentInfo` containing an Authenticode `SignedData`.
+    pub fn from_der(data: &[u8]) -> io::Result<Self> {
+        let mut signed_data = signed_data(DerReader::new(data).expect(SEQUENCE)?)?;
+
+        let _version = signed_data.expect(INTEGER)?;
+        let _digest_algorithms = signed_data.expect(SET)?;
+
+        let mut content_info = signed_data.expect(SEQUENCE)?.reader();
+        if content_info.expect(OBJECT_IDENTIFIER)?.contents() != SPC_INDIRECT_DATA {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Signed data is not Authenticode",
+            ));
+        }
+
+        // SpcIndirectDataContent
+        let mut indirect_data = content_info
+            .expect(context(0))?
+            .reader()
+            .expect(SEQUENCE)?
+            .reader();
+        let _data = indirect_data.expect(SEQUENCE)?;
+
+        let mut digest_info = indirect_data.expect(SEQUENCE)?.reader();
+        let digest_algorithm = digest_info
+            .expect(SEQUENCE)?
+            .reader()
+            .expect(OBJECT_IDENTIFIER)?;
+        let digest = digest_info.expect(OCTET_STRING)?.contents().to_vec();
+
+        let certificates = signed_data.optional(context(0))?;
+        let _crls = signed_data.optional(context(1))?;
+
+        let signer_info = SignerInfo::parse(signed_data.expect(SET)?.reader().expect(SEQUENCE)?)?;
+
+        let signer = certificates
+            .and_then(|certificates| {
+                find_subject(certificates, signer_info.issuer, signer_info.serial_number)
+            })
+            .map(Signer::from_name)
+            .unwrap_or_default();
+
+        Ok(Self {
+            signer,
+            signing_time: signer_info.signing_time(),
+            digest_algorithm: DigestAlgorithm::from_oid(digest_algorithm.contents()),
+            digest,
+        })
+    }
+
+    /// Computes the Authenticode hash of the PE image and compares it to the signed digest.
+    ///
+    /// Returns `None` if the digest algorithm is not one that can be computed, such as the
+    /// deprecated MD5 and SHA-1 algorithms.
+    pub fn matches_image<R: Read + Seek>(&self, reader: R, pe: &PE) -> io::Result<Option<bool>> {
+        let excluded = excluded_ranges(pe);
+
+        let image_digest = match self.digest_algorithm {
+            Some(DigestAlgorithm::Sha256) => image_digest::<Sha256, _>(reader, excluded)?,
+            Some(DigestAlgorithm::Sha384) => image_digest::<Sha384, _>(reader, excluded)?,
+            Some(DigestAlgorithm::Sha512) => image_digest::<Sha512, _>(reader, excluded)?,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(image_digest == self.digest))
+    }
+}
+
+impl Signer {
+    /// Reads the common name and organization from an X.501 `Name`.
+    fn from_name(name: Element) -> Self {
+        let mut signer = Self::default();
+
+        for attribute in name
+            .reader()
+            .flatten()
+            .flat_map(|relative_name| relative_name.reader().flatten())
+        {
+            let mut attribute = attribute.reader();
+            let (Ok(oid), Ok(value)) = (attribute.expect(OBJECT_IDENTIFIER), attribute.read())
+            else {
+                continue;
+            };
+
+            match oid.contents() {
+                COMMON_NAME => signer.common_name = value.as_string(),
+                ORGANIZATION_NAME => signer.organization = value.as_string(),
+                _ => {}
+            }
+        }
+
+        signer
+    }
+}
+
+/// Unwraps a PKCS #7 `ContentInfo`, returning a reader over the fields of its `SignedData`.
+fn signed_data(content_info: Element) -> io::Result<DerReader> {
+    let mut content_info = content_info.reader();
+
+    if content_info.expect(OBJECT_IDENTIFIER)?.contents() != SIGNED_DATA {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Content info is not signed data",
+        ));
+    }
+
+    Ok(content_info
+        .expect(context(0))?
+        .reader()
+        .expect(SEQUENCE)?
+        .reader())
+}
+
+/// Finds the subject of the certificate with the given issuer and serial number.
+fn find_subject<'data>(
+    certificates: Element<'data>,
+    issuer: &[u8],
+    serial_number: &[u8],
+) -> Option<Element<'data>> {
+    certificates.reader().flatten().find_map(|certificate| {
+        let mut tbs_certificate = certificate.reader().expect(SEQUENCE).ok()?.reader();
+
+        let _version = tbs_certificate.optional(context(0)).ok()?;
+        let certificate_serial_number = tbs_certificate.expect(INTEGER).ok()?;
+        let _signature = tbs_certificate.expect(SEQUENCE).ok()?;
+        let certificate_issuer = tbs_certificate.expect(SEQUENCE).ok()?;
+        let _validity = tbs_certificate.expect(SEQUENCE).ok()?;
+        let subject = tbs_certificate.expect(SEQUENCE).ok()?;
+
+        (certificate_issuer.raw() == issuer
+            && certificate_serial_number.contents() == serial_number)
+            .then_some(subject)
+    })
+}
+
+/// Returns the sorted `(offset, length)` ranges of the file that are left out of the Authenticode
+/// hash: the checksum, the certificate table's data directory entry, and the certificate table.
+///
+/// See <https://learn.microsoft.com/windows/win32/debug/pe-format#process-for-generating-the-authenticode-pe-image-hash>.
+fn excluded_ranges(pe: &PE) -> [(u64, u64); 3] {
+    const SIGNATURE_SIZE: u64 = 4;
+    const COFF_HEADER_SIZE: u64 = 20;
+    const CHECKSUM_OFFSET: u64 = 64;
+    const DATA_DIRECTORY_SIZE: u64 = 8;
+    const CERTIFICATE_TABLE_INDEX: u64 = 4;
+
+    let optional_header_offset =
+        u64::from(pe.dos_header.pe_pointer()) + SIGNATURE_SIZE + COFF_HEADER_SIZE;
+
+    let data_directories_offset = optional_header_offset
+        + match pe.optional_header.standard_fields {
+            StandardFields::PE32(_) => 96,
+            StandardFields::PE64(_) => 112,
+        };
+
+    let (certificate_table_offset, certificate_table_size) =
+        pe.certificate_table().map_or((0, 0), |table| {
+            (table.virtual_address().into(), table.size().into())
+        });
+
+    let mut ranges = [
+        (optional_header_offset + CHECKSUM_OFFSET, 4),
+        (
+            data_directories_offset + CERTIFICATE_TABLE_INDEX * DATA_DIRECTORY_SIZE,
+            DATA_DIRECTORY_SIZE,
+        ),
+        (certificate_table_offset, certificate_table_size),
+    ];
+    ranges.sort_unstable();
+    ranges
+}
+
+/// Digests the whole reader except for the given sorted `(offset, length)` ranges.
+fn image_digest<D: Digest, R: Read + Seek>(
+    mut reader: R,
+    excluded: impl IntoIterator<Item = (u64, u64)>,
+) -> io::Result<Vec<u8>> {
+    let mut digest = D::new();
+    let mut buffer = [0; 1 << 13];
+
+    let end = reader.seek(SeekFrom::End(0))?;
+    let mut position = 0;
+
+    for (offset, length) in excluded.into_iter().chain([(end, 0)]) {
+        reader.seek(SeekFrom::Start(position))?;
+        let mut section = (&mut reader).take(offset.saturating_sub(position));
+
+        loop {
+            let count = section.read(&mut buffer)?;
+            if count == 0 {
+                break;
+            }
+            digest.update(&buffer[..count]);
+        }
+
+        position = position.max(offset + length);
+    }
+
+    Ok(digest.finalize().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use chrono::{TimeZone, Utc};
+    use sha2::{Digest, Sha256};
+
+    use super::{
+        Authenticode, COMMON_NAME, DigestAlgorithm, ORGANIZATION_NAME, SIGNED_DATA,
+        SPC_INDIRECT_DATA, Signer,
+        der::{
+            GENERALIZED_TIME, INTEGER, OBJECT_IDENTIFIER, OCTET_STRING, SEQUENCE, SET, UTF8_STRING,
+            context,
+        },
+        image_digest,
+    };
+
+    const SHA256_OID: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+    const RFC3161_COUNTER_SIGNATURE: &[u8] =
+        &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x03, 0x03, 0x01];
+
+    fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut element = vec![tag];
+        match contents.len() {
+            length @ ..0x80 => element.push(length as u8),
+            length @ ..0x100 => element.extend([0x81, length as u8]),
+            length => element.extend([0x82, (length >> 8) as u8, length as u8]),
+        }
+        element.extend(contents);
+        element
+    }
+
+    fn constructed(tag: u8, children: &[Vec<u8>]) -> Vec<u8> {
+        tlv(tag, &children.concat())
+    }
+
+    fn name(attributes: &[(&[u8], &str)]) -> Vec<u8> {
+        let relative_names = attributes
+            .iter()
+            .map(|(oid, value)| {
+                constructed(
+                    SET,
+                    &[constructed(
+                        SEQUENCE,
+                        &[
+                            tlv(OBJECT_IDENTIFIER, oid),
+                            tlv(UTF8_STRING, value.as_bytes()),
+                        ],
+                    )],
+                )
+            })
+            .collect::<Vec<_>>();
+        constructed(SEQUENCE, &relative_names)
+    }
+
+    fn algorithm(oid: &[u8]) -> Vec<u8> {
+        constructed(SEQUENCE, &[tlv(OBJECT_IDENTIFIER, oid)])
+    }
+
+    fn certificate(serial_number: u8, issuer: &[u8], subject: &[u8]) -> Vec<u8> {
+        constructed(
+            SEQUENCE,
+            &[
+                constructed(
+                    SEQUENCE,
+                    &[
+                        constructed(context(0), &[tlv(INTEGER, &[2])]),
+                        tlv(INTEGER, &[serial_number]),
+                        algorithm(SHA256_OID),
+                        issuer.to_vec(),
+                        tlv(SEQUENCE, &[]),
+                        subject.to_vec(),
+                    ],
+                ),
+                algorithm(SHA256_OID),
+                tlv(0x03, &[0]),
+            ],
+        )
+    }
+
+    fn time_stamp_token(time: &str) -> Vec<u8> {
+        let tst_info = constructed(
+            SEQUENCE,
+            &[
+                tlv(INTEGER, &[1]),
+                tlv(OBJECT_IDENTIFIER, &[0x2A, 0x03]),
+                tlv(SEQUENCE, &[]),
+                tlv(INTEGER, &[7]),
+                tlv(GENERALIZED_TIME, time.as_bytes()),
+            ],
+        );
+
+        constructed(
+            SEQUENCE,
+            &[
+                tlv(OBJECT_IDENTIFIER, SIGNED_DATA),
+                constructed(
+                    context(0),
+                    &[constructed(
+                        SEQUENCE,
+                        &[
+                            tlv(INTEGER, &[3]),
+                            tlv(SET, &[]),
+                            constructed(
+                                SEQUENCE,
+                                &[
+                                    tlv(OBJECT_IDENTIFIER, &[0x2A, 0x04]),
+                                    constructed(context(0), &[tlv(OCTET_STRING, &tst_info)]),
+                                ],
+                            ),
+                        ],
+                    )],
+                ),
+            ],
+        )
+    }
+
+    fn authenticode(digest: &[u8]) -> Vec<u8> {
+        let issuer = name(&[(COMMON_NAME, "Example Code Signing CA")]);
+        let subject = name(&[
+            (COMMON_NAME, "Example Software"),
+            (ORGANIZATION_NAME, "Example Corporation"),
+        ]);
+
+        let signer_info = constructed(
+            SEQUENCE,
+            &[
+                tlv(INTEGER, &[1]),
+                constructed(SEQUENCE, &[issuer.clone(), tlv(INTEGER, &[0x42])]),
+                algorithm(SHA256_OID),
+                algorithm(&[0x2A, 0x05]),
+                tlv(OCTET_STRING, &[0; 4]),
+                constructed(
+                    context(1),
+                    &[constructed(
+                        SEQUENCE,
+                        &[
+                            tlv(OBJECT_IDENTIFIER, RFC3161_COUNTER_SIGNATURE),
+                            constructed(SET, &[time_stamp_token("20240315120000Z")]),
+                        ],
+                    )],
+                ),
+            ],
+        );
+
+        constructed(
+            SEQUENCE,
+            &[
+                tlv(OBJECT_IDENTIFIER, SIGNED_DATA),
+                constructed(
+                    context(0),
+                    &[constructed(
+                        SEQUENCE,
+                        &[
+                            tlv(INTEGER, &[1]),
+                            constructed(SET, &[algorithm(SHA256_OID)]),
+                            constructed(
+                                SEQUENCE,
+                                &[
+                                    tlv(OBJECT_IDENTIFIER, SPC_INDIRECT_DATA),
+                                    constructed(
+                                        context(0),
+                                        &[constructed(
+                                            SEQUENCE,
+                                            &[
+                                                tlv(SEQUENCE, &[]),
+                                                constructed(
+                                                    SEQUENCE,
+                                                    &[
+                                                        algorithm(SHA256_OID),
+                                                        tlv(OCTET_STRING, digest),
+                                                    ],
+                                                ),
+                                            ],
+                                        )],
+                                    ),
+                                ],
+                            ),
+                            constructed(
+                                context(0),
+                                &[
+                                    certificate(0x01, &issuer, &issuer),
+                                    certificate(0x42, &issuer, &subject),
+                                ],
+                            ),
+                            constructed(SET, &[signer_info]),
+                        ],
+                    )],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn from_der() {
+        let authenticode = Authenticode::from_der(&authenticode(&[0xAB; 32])).unwrap();
+
+        assert_eq!(
+            authenticode.signer,
+            Signer {
+                common_name: Some("Example Software".to_owned()),
+                organization: Some("Example Corporation".to_owned()),
+            }
+        );
+        assert_eq!(
+            authenticode.signing_time,
+            Some(Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap())
+        );
+        assert_eq!(authenticode.digest_algorithm, Some(DigestAlgorithm::Sha256));
+        assert_eq!(authenticode.digest, [0xAB; 32]);
+    }
+
+    #[test]
+    fn not_signed_data() {
+        let data = constructed(SEQUENCE, &[tlv(OBJECT_IDENTIFIER, SPC_INDIRECT_DATA)]);
+        assert!(Authenticode::from_der(&data).is_err());
+    }
+
+    #[test]
+    fn image_digest_skips_excluded_ranges() {
+        let data = b"0123456789abcdef";
+
+        let digest = image_digest::<Sha256, _>(Cursor::new(data), [(2, 2), (8, 4)]).unwrap();
+
+        assert_eq!(digest, Sha256::digest(b"014567cdef").to_vec());
+    }
+}
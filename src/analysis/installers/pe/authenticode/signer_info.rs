@@ -0,0 +1,124 @@
+use std::io;
+
+use chrono::{DateTime, Utc};
+
+use super::der::{
+    DerReader, Element, GENERALIZED_TIME, INTEGER, OBJECT_IDENTIFIER, OCTET_STRING, SEQUENCE, SET,
+    context,
+};
+
+/// `1.2.840.113549.1.9.5`
+const SIGNING_TIME: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x05];
+
+/// `1.2.840.113549.1.9.6`
+const COUNTER_SIGNATURE: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x06];
+
+/// `1.3.6.1.4.1.311.3.3.1`
+const RFC3161_COUNTER_SIGNATURE: &[u8] =
+    &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x03, 0x03, 0x01];
+
+/// The parts of a PKCS #7 `SignerInfo` needed to identify the signer and when they signed.
+///
+/// See <https://datatracker.ietf.org/doc/html/rfc2315#section-9.2>.
+#[derive(Copy, Clone, Debug)]
+pub struct SignerInfo<'data> {
+    /// The full encoding of the issuer name of the signer's certificate.
+    pub issuer: &'data [u8],
+
+    /// The serial number of the signer's certificate.
+    pub serial_number: &'data [u8],
+
+    authenticated_attributes: Option<Element<'data>>,
+
+    unauthenticated_attributes: Option<Element<'data>>,
+}
+
+impl<'data> SignerInfo<'data> {
+    pub fn parse(signer_info: Element<'data>) -> io::Result<Self> {
+        let mut reader = signer_info.reader();
+
+        let _version = reader.expect(INTEGER)?;
+
+        let mut issuer_and_serial_number = reader.expect(SEQUENCE)?.reader();
+        let issuer = issuer_and_serial_number.expect(SEQUENCE)?.raw();
+        let serial_number = issuer_and_serial_number.expect(INTEGER)?.contents();
+
+        let _digest_algorithm = reader.expect(SEQUENCE)?;
+        let authenticated_attributes = reader.optional(context(0))?;
+        let _digest_encryption_algorithm = reader.expect(SEQUENCE)?;
+        let _encrypted_digest = reader.expect(OCTET_STRING)?;
+        let unauthenticated_attributes = reader.optional(context(1))?;
+
+        Ok(Self {
+            issuer,
+            serial_number,
+            authenticated_attributes,
+            unauthenticated_attributes,
+        })
+    }
+
+    /// Returns the time the signature was made.
+    ///
+    /// Authenticode signatures rarely include a signing time themselves. Instead, the time comes
+    /// from a timestamp counter-signature, which is either a legacy PKCS #9 counter-signature or an
+    /// RFC 3161 time-stamp token.
+    pub fn signing_time(&self) -> Option<DateTime<Utc>> {
+        self.authenticated_attributes
+            .and_then(|attributes| attribute_value(attributes, SIGNING_TIME))
+            .and_then(Element::as_time)
+            .or_else(|| {
+                let attributes = self.unauthenticated_attributes?;
+
+                attribute_value(attributes, COUNTER_SIGNATURE)
+                    .and_then(|counter_signature| Self::parse(counter_signature).ok())
+                    .and_then(|counter_signature| counter_signature.signing_time())
+                    .or_else(|| {
+                        attribute_value(attributes, RFC3161_COUNTER_SIGNATURE)
+                            .and_then(|token| time_stamp_token_time(token).ok())
+                    })
+            })
+    }
+}
+
+/// Returns the first value of the attribute with the given object identifier.
+fn attribute_value<'data>(attributes: Element<'data>, oid: &[u8]) -> Option<Element<'data>> {
+    attributes.reader().flatten().find_map(|attribute| {
+        let mut attribute = attribute.reader();
+
+        if attribute.expect(OBJECT_IDENTIFIER).ok()?.contents() == oid {
+            attribute.expect(SET).ok()?.reader().read().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Reads the generation time from the `TSTInfo` inside an RFC 3161 time-stamp token.
+///
+/// See <https://datatracker.ietf.org/doc/html/rfc3161#section-2.4.2>.
+fn time_stamp_token_time(token: Element) -> io::Result<DateTime<Utc>> {
+    let mut signed_data = super::signed_data(token)?;
+
+    let _version = signed_data.expect(INTEGER)?;
+    let _digest_algorithms = signed_data.expect(SET)?;
+
+    let mut encapsulated_content_info = signed_data.expect(SEQUENCE)?.reader();
+    let _content_type = encapsulated_content_info.expect(OBJECT_IDENTIFIER)?;
+    let tst_info = encapsulated_content_info
+        .expect(context(0))?
+        .reader()
+        .expect(OCTET_STRING)?;
+
+    let mut tst_info = DerReader::new(tst_info.contents())
+        .expect(SEQUENCE)?
+        .reader();
+    let _version = tst_info.expect(INTEGER)?;
+    let _policy = tst_info.expect(OBJECT_IDENTIFIER)?;
+    let _message_imprint = tst_info.expect(SEQUENCE)?;
+    let _serial_number = tst_info.expect(INTEGER)?;
+
+    tst_info
+        .expect(GENERALIZED_TIME)?
+        .as_time()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid time-stamp time"))
+}
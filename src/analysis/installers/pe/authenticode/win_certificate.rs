@@ -0,0 +1,75 @@
+use std::fmt;
+
+use zerocopy::{FromBytes, Immutable, KnownLayout, LE, U16, U32, Unaligned};
+
+/// The header of an attribute certificate in the certificate table of a PE file.
+///
+/// See <https://learn.microsoft.com/windows/win32/api/wintrust/ns-wintrust-win_certificate>.
+#[derive(Clone, Copy, FromBytes, KnownLayout, Immutable, Unaligned)]
+#[repr(C)]
+pub struct WinCertificate {
+    length: U32<LE>,
+    revision: U16<LE>,
+    certificate_type: U16<LE>,
+}
+
+impl WinCertificate {
+    #[doc(alias = "WIN_CERT_REVISION_2_0")]
+    pub const REVISION_2_0: u16 = 0x0200;
+
+    #[doc(alias = "WIN_CERT_TYPE_PKCS_SIGNED_DATA")]
+    pub const TYPE_PKCS_SIGNED_DATA: u16 = 0x0002;
+
+    /// Returns the length of the attribute certificate, including this header.
+    #[inline]
+    pub const fn length(&self) -> u32 {
+        self.length.get()
+    }
+
+    /// Returns the length of the certificate data that follows this header.
+    #[inline]
+    pub const fn data_length(&self) -> u32 {
+        self.length().saturating_sub(size_of::<Self>() as u32)
+    }
+
+    #[inline]
+    pub const fn revision(&self) -> u16 {
+        self.revision.get()
+    }
+
+    #[inline]
+    pub const fn certificate_type(&self) -> u16 {
+        self.certificate_type.get()
+    }
+
+    /// Returns `true` if the certificate data is a PKCS #7 `SignedData` structure.
+    #[inline]
+    pub const fn is_pkcs_signed_data(&self) -> bool {
+        self.certificate_type() == Self::TYPE_PKCS_SIGNED_DATA
+    }
+}
+
+impl fmt::Debug for WinCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WIN_CERTIFICATE")
+            .field("dwLength", &self.length())
+            .field("wRevision", &self.revision())
+            .field("wCertificateType", &self.certificate_type())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WinCertificate;
+
+    #[test]
+    fn size() {
+        assert_eq!(size_of::<WinCertificate>(), 8);
+    }
+
+    #[test]
+    fn alignment() {
+        assert_eq!(align_of::<WinCertificate>(), 1);
+    }
+}
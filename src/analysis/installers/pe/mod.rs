@@ -1,5 +1,6 @@
 #![expect(unused)]
 
+pub mod authenticode;
 mod coff;
 pub mod dos;
 pub mod optional_header;
@@ -14,6 +15,7 @@
     io::{Error, Read, Seek, SeekFrom, Take},
 };
 
+pub use authenticode::Authenticode;
 pub use coff::CoffHeader;
 pub use dos::DosHeader;
 pub use optional_header::OptionalHeader;
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+/// The configuration of a modified 7-Zip SFX module (such as `7zSD.sfx`), which sits between the
+/// SFX stub and the 7z archive.
+///
+/// ```text
+/// ;!@Install@!UTF-8!
+/// Title="Example"
+/// RunProgram="setup.exe /S"
+/// ;!@InstallEnd@!
+/// ```
+///
+/// See <https://documentation.help/7-Zip/sfx.htm>.
+#[derive(Debug, Default)]
+pub struct Config(HashMap<String, String>);
+
+impl Config {
+    const BEGIN: &[u8] = b";!@Install@!UTF-8!";
+
+    const END: &[u8] = b";!@InstallEnd@!";
+
+    /// Finds and parses the SFX configuration in the given data, if present.
+    pub fn find(data: &[u8]) -> Option<Self> {
+        let start = memchr::memmem::find(data, Self::BEGIN)? + Self::BEGIN.len();
+        let end = start + memchr::memmem::find(&data[start..], Self::END)?;
+
+        let config = String::from_utf8_lossy(&data[start..end]);
+
+        Some(Self(
+            config
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .map(|(key, value)| {
+                    (
+                        key.trim().to_owned(),
+                        value.trim().trim_matches('"').replace(r#"\""#, r#"""#),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    /// Returns the value of the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    /// Returns the file run after extraction, without any arguments.
+    pub fn run_program(&self) -> Option<&str> {
+        self.command().map(|(program, _arguments)| program)
+    }
+
+    /// Returns whether the file run after extraction is given the arguments that the SFX itself
+    /// was run with, which the modified SFX module substitutes for `%%P` in `RunProgram`.
+    ///
+    /// Otherwise, switches such as `/VERYSILENT` passed to the SFX never reach the nested
+    /// installer.
+    pub fn forwards_arguments(&self) -> bool {
+        const FORWARDED_ARGUMENTS: &str = "%%P";
+
+        self.command().is_some_and(|(_program, arguments)| {
+            arguments.to_ascii_uppercase().contains(FORWARDED_ARGUMENTS)
+        })
+    }
+
+    /// Returns the file run after extraction and the arguments it is run with.
+    fn command(&self) -> Option<(&str, &str)> {
+        const RUN_PROGRAM: &str = "RunProgram";
+        const EXECUTE_FILE: &str = "ExecuteFile";
+
+        let program = self.get(RUN_PROGRAM).or_else(|| self.get(EXECUTE_FILE))?;
+
+        // Strip any SFX prefixes such as `hidcon:` or `fm0:`
+        let mut program = program.trim();
+        while let Some((prefix, rest)) = program.split_once(':')
+            && prefix.len() > 1
+            && prefix.bytes().all(|byte| byte.is_ascii_alphanumeric())
+        {
+            program = rest.trim_start();
+        }
+
+        let (program, arguments) = program
+            .strip_prefix('"')
+            .and_then(|program| program.split_once('"'))
+            .or_else(|| program.split_once(char::is_whitespace))
+            .unwrap_or((program, ""));
+
+        Some((program, arguments.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use rstest::rstest;
+
+    use super::Config;
+
+    #[rstest]
+    #[case::plain(r#"RunProgram="setup.exe""#, Some("setup.exe"))]
+    #[case::arguments(r#"RunProgram="setup.exe /S""#, Some("setup.exe"))]
+    #[case::quoted(r#"RunProgram="\"My Setup.exe\" /S""#, Some("My Setup.exe"))]
+    #[case::prefix(r#"RunProgram="hidcon:setup.exe""#, Some("setup.exe"))]
+    #[case::execute_file(r#"ExecuteFile="install.msi""#, Some("install.msi"))]
+    #[case::none(r#"Title="Example""#, None)]
+    fn run_program(#[case] line: &str, #[case] expected: Option<&str>) {
+        let data = format!(";!@Install@!UTF-8!\r\n{line}\r\n;!@InstallEnd@!7z");
+        let config = Config::find(data.as_bytes()).unwrap();
+        assert_eq!(config.run_program(), expected);
+    }
+
+    #[rstest]
+    #[case::forwarded(r#"RunProgram="setup.exe %%P""#, true)]
+    #[case::quoted(r#"RunProgram="\"My Setup.exe\" /S %%p""#, true)]
+    #[case::fixed(r#"RunProgram="setup.exe /S""#, false)]
+    #[case::plain(r#"RunProgram="setup.exe""#, false)]
+    #[case::none(r#"Title="Example""#, false)]
+    fn forwards_arguments(#[case] line: &str, #[case] expected: bool) {
+        let data = format!(";!@Install@!UTF-8!\r\n{line}\r\n;!@InstallEnd@!7z");
+        let config = Config::find(data.as_bytes()).unwrap();
+        assert_eq!(config.forwards_arguments(), expected);
+    }
+
+    #[test]
+    fn find() {
+        const DATA: &[u8] = indoc! {br#"
+            MZ...
+            ;!@Install@!UTF-8!
+            Title="Example"
+            BeginPrompt="Do you want to install Example?"
+            ;!@InstallEnd@!
+        "#};
+
+        let config = Config::find(DATA).unwrap();
+        assert_eq!(config.get("Title"), Some("Example"));
+        assert_eq!(
+            config.get("BeginPrompt"),
+            Some("Do you want to install Example?")
+        );
+    }
+
+    #[test]
+    fn missing() {
+        assert!(Config::find(b"7z\xBC\xAF\x27\x1C").is_none());
+    }
+}
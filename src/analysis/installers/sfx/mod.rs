@@ -0,0 +1,241 @@
+mod config;
+
+use std::{
+    io,
+    io::{Read, Seek, SeekFrom},
+};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use config::Config;
+use sevenz_rust2::{ArchiveReader, Password};
+use thiserror::Error;
+use tracing::debug;
+use winget_types::installer::{Architecture, Installer, InstallerType, Switches};
+
+use crate::{
+    analysis::{
+        Analyzer, Installers,
+        explain::{ARCHITECTURE, INSTALLER_TYPE, explain},
+        installers::pe::{PE, resource::SectionReader},
+    },
+    traits::FromMachine,
+};
+
+#[derive(Error, Debug)]
+pub enum SfxError {
+    #[error("File is not a self-extracting archive")]
+    NotSfxFile,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// The archive format of a self-extracting archive.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum ArchiveFormat {
+    SevenZip,
+    Rar,
+}
+
+/// A 7-Zip or WinRAR self-extracting archive, where the archive is appended to the overlay of a PE
+/// stub.
+pub struct Sfx {
+    architecture: Architecture,
+    nested_installers: Vec<Installer>,
+    forwards_arguments: bool,
+}
+
+impl Sfx {
+    const SEVEN_ZIP_SIGNATURE: &[u8; 6] = b"7z\xBC\xAF\x27\x1C";
+
+    /// The signature of a RAR 1.5 to 4.x archive.
+    const RAR4_SIGNATURE: &[u8; 7] = b"Rar!\x1A\x07\x00";
+
+    /// The signature of a RAR 5.0 archive.
+    const RAR5_SIGNATURE: &[u8; 8] = b"Rar!\x1A\x07\x01\x00";
+
+    /// The number of bytes after the overlay offset searched for an archive signature.
+    ///
+    /// Modified 7-Zip SFX modules store their configuration before the archive.
+    const SEARCH_SIZE: u64 = 1 << 16;
+
+    const VALID_NESTED_FILE_EXTENSIONS: [&str; 2] = ["msi", "exe"];
+
+    pub fn new<R: Read + Seek>(mut reader: R, pe: &PE) -> Result<Self, SfxError> {
+        let overlay_offset = pe.overlay_offset().ok_or(SfxError::NotSfxFile)?;
+
+        reader.seek(SeekFrom::Start(overlay_offset))?;
+        let mut overlay = Vec::new();
+        reader
+            .by_ref()
+            .take(Self::SEARCH_SIZE)
+            .read_to_end(&mut overlay)?;
+
+        let (format, archive_offset) = [
+            (
+                ArchiveFormat::SevenZip,
+                Self::SEVEN_ZIP_SIGNATURE.as_slice(),
+            ),
+            (ArchiveFormat::Rar, Self::RAR4_SIGNATURE.as_slice()),
+            (ArchiveFormat::Rar, Self::RAR5_SIGNATURE.as_slice()),
+        ]
+        .into_iter()
+        .filter_map(|(format, signature)| {
+            memchr::memmem::find(&overlay, signature).map(|position| (format, position))
+        })
+        .min_by_key(|&(_format, position)| position)
+        .ok_or(SfxError::NotSfxFile)?;
+
+        let config = Config::find(&overlay[..archive_offset]);
+        debug!(?format, ?config);
+
+        let nested_installers = match format {
+            ArchiveFormat::SevenZip => {
+                let archive_reader = SectionReader::from_offset(
+                    &mut reader,
+                    overlay_offset + archive_offset as u64,
+                )?;
+                let mut archive =
+                    ArchiveReader::new(archive_reader, Password::empty()).map_err(|error| {
+                        debug!(%error, "Failed to read the 7z archive");
+                        SfxError::NotSfxFile
+                    })?;
+
+                let files = archive
+                    .archive()
+                    .files
+                    .iter()
+                    .filter(|entry| !entry.is_directory())
+                    .map(|entry| Utf8PathBuf::from(entry.name().replace('\\', "/")))
+                    .collect::<Vec<_>>();
+                debug!(?files);
+
+                Self::nested_file(&files, config.as_ref())
+                    .map(|nested_file| Self::analyze_nested(&mut archive, nested_file))
+                    .transpose()?
+                    .unwrap_or_default()
+            }
+            // RAR archives cannot be extracted, so their nested installer is unknown
+            ArchiveFormat::Rar => Vec::new(),
+        };
+
+        explain(
+            INSTALLER_TYPE,
+            InstallerType::Exe,
+            "SFX",
+            format_args!("the overlay contains a {format:?} archive"),
+        );
+
+        let architecture = Architecture::from_machine(pe.machine());
+        if nested_installers.is_empty() {
+            explain(
+                ARCHITECTURE,
+                architecture,
+                "SFX",
+                format_args!(
+                    "no nested installer was analyzed, so the machine type of the SFX stub, {:#06X}, is used",
+                    pe.machine()
+                ),
+            );
+        }
+
+        Ok(Self {
+            architecture,
+            nested_installers,
+            forwards_arguments: config.as_ref().is_some_and(Config::forwards_arguments),
+        })
+    }
+
+    /// Returns the file that is run after extraction.
+    ///
+    /// This is the file specified by the SFX configuration if there is one. Otherwise, it is the
+    /// only MSI or EXE in the archive, if there is exactly one.
+    fn nested_file<'files>(
+        files: &'files [Utf8PathBuf],
+        config: Option<&Config>,
+    ) -> Option<&'files Utf8Path> {
+        if let Some(run_program) = config.and_then(Config::run_program) {
+            let run_program = run_program.replace('\\', "/");
+            return files
+                .iter()
+                .find(|file| file.as_str().eq_ignore_ascii_case(&run_program))
+                .map(Utf8PathBuf::as_path);
+        }
+
+        let mut candidates = files.iter().filter(|file| {
+            file.extension().is_some_and(|extension| {
+                Self::VALID_NESTED_FILE_EXTENSIONS
+                    .iter()
+                    .any(|valid_extension| extension.eq_ignore_ascii_case(valid_extension))
+            })
+        });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(candidate), None) => Some(candidate.as_path()),
+            _ => None,
+        }
+    }
+
+    /// Extracts the nested file to a temporary file and analyzes it.
+    fn analyze_nested<R: Read + Seek>(
+        archive: &mut ArchiveReader<R>,
+        nested_file: &Utf8Path,
+    ) -> Result<Vec<Installer>, SfxError> {
+        let mut temp_file = tempfile::tempfile()?;
+
+        // An archive that cannot be extracted, such as one that is encrypted or uses an
+        // unsupported codec, is treated as a generic executable rather than failing the analysis
+        archive
+            .for_each_entries(|entry, entry_reader| {
+                if Utf8Path::new(&entry.name().replace('\\', "/")) == nested_file {
+                    io::copy(entry_reader, &mut temp_file)?;
+                    return Ok(false);
+                }
+                Ok(true)
+            })
+            .map_err(|error| {
+                debug!(%nested_file, %error, "Failed to extract the nested file");
+                SfxError::NotSfxFile
+            })?;
+
+        temp_file.seek(SeekFrom::Start(0))?;
+
+        match Analyzer::new(&mut temp_file, nested_file.as_str()) {
+            Ok(analyzer) => Ok(analyzer.installers),
+            Err(error) => {
+                debug!(%nested_file, %error);
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+impl Installers for Sfx {
+    fn installers(&self) -> Vec<Installer> {
+        if self.nested_installers.is_empty() {
+            return vec![Installer {
+                architecture: self.architecture,
+                r#type: Some(InstallerType::Exe),
+                ..Installer::default()
+            }];
+        }
+
+        self.nested_installers
+            .iter()
+            .cloned()
+            .map(|installer| match installer.r#type {
+                // The nested installer's switches only work if the SFX passes its arguments on
+                Some(
+                    InstallerType::Exe
+                    | InstallerType::Inno
+                    | InstallerType::Nullsoft
+                    | InstallerType::Burn,
+                ) if self.forwards_arguments => installer,
+                _ => Installer {
+                    r#type: Some(InstallerType::Exe),
+                    switches: Switches::default(),
+                    ..installer
+                },
+            })
+            .collect()
+    }
+}
@@ -22,6 +22,7 @@
 use crate::{
     analysis::{
         Installers,
+        explain::{ARCHITECTURE, INSTALLER_TYPE, SCOPE, explain},
         installers::pe::{PE, resource::SectionReader},
     },
     traits::FromMachine,
@@ -117,14 +118,47 @@ pub fn new<R: Read + Seek>(mut reader: R, pe: &PE) -> Result<Self, SquirrelError
             })
             .map(Utf8Path::to_path_buf);
 
+        let source = if is_velopack { "Velopack" } else { "Squirrel" };
+        explain(
+            INSTALLER_TYPE,
+            InstallerType::Exe,
+            source,
+            format_args!("the setup contains the package {nuspec_filename}"),
+        );
+        explain(
+            SCOPE,
+            Scope::User,
+            source,
+            format_args!("{source} always installs to the user's local app data"),
+        );
+
         let architecture = entrypoint
             .and_then(|entrypoint| {
                 let reader = nupkg.by_name(entrypoint.as_str()).ok()?;
 
                 let machine = machine_from_exe_reader(reader).ok()?;
-                Some(Architecture::from_machine(machine))
+                let architecture = Architecture::from_machine(machine);
+                explain(
+                    ARCHITECTURE,
+                    architecture,
+                    source,
+                    format_args!("the machine type of the main executable {entrypoint} is {machine:#06X}"),
+                );
+                Some(architecture)
             })
-            .unwrap_or_else(|| Architecture::from_machine(pe.machine()));
+            .unwrap_or_else(|| {
+                let architecture = Architecture::from_machine(pe.machine());
+                explain(
+                    ARCHITECTURE,
+                    architecture,
+                    source,
+                    format_args!(
+                        "the main executable was not found, and the machine type of the setup is {:#06X}",
+                        pe.machine()
+                    ),
+                );
+                architecture
+            });
 
         Ok(Self {
             architecture,
@@ -0,0 +1,132 @@
+use winget_types::installer::{FileExtension, Protocol};
+
+/// A predefined registry key that an installer writes values under.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RegistryRoot {
+    ClassesRoot,
+    CurrentUser,
+    LocalMachine,
+    Users,
+    /// `HKEY_CURRENT_USER` for per-user installs and `HKEY_LOCAL_MACHINE` for per-machine installs.
+    CurrentUserOrLocalMachine,
+}
+
+/// The value that marks a class key as a URL protocol handler.
+///
+/// See <https://learn.microsoft.com/previous-versions/windows/internet-explorer/ie-developer/platform-apis/aa767914(v=vs.85)>.
+const URL_PROTOCOL: &str = "URL Protocol";
+
+/// The subkey of `HKEY_CURRENT_USER` and `HKEY_LOCAL_MACHINE` that is merged into
+/// `HKEY_CLASSES_ROOT`.
+const SOFTWARE_CLASSES: &str = r"Software\Classes\";
+
+impl RegistryRoot {
+    /// Returns the path of a key relative to `HKEY_CLASSES_ROOT`, if it is a class key.
+    pub fn classes_key(self, key: &str) -> Option<&str> {
+        let key = key.trim_matches('\\');
+
+        match self {
+            Self::ClassesRoot => Some(key),
+            Self::CurrentUser | Self::LocalMachine | Self::CurrentUserOrLocalMachine => key
+                .get(..SOFTWARE_CLASSES.len())
+                .filter(|prefix| prefix.eq_ignore_ascii_case(SOFTWARE_CLASSES))
+                .map(|_| &key[SOFTWARE_CLASSES.len()..]),
+            Self::Users => None,
+        }
+    }
+
+    /// Returns the file extension that a key registers a file association for.
+    ///
+    /// Both the `.ext` key itself and any of its subkeys, such as `.ext\OpenWithProgids`, count as
+    /// associating the extension.
+    pub fn file_extension(self, key: &str) -> Option<FileExtension> {
+        let extension = self
+            .classes_key(key)?
+            .split('\\')
+            .next()?
+            .strip_prefix('.')?;
+
+        if is_unresolved(extension) {
+            return None;
+        }
+
+        FileExtension::new(extension.to_ascii_lowercase()).ok()
+    }
+
+    /// Returns the URL protocol that a registry value registers, if the value is the
+    /// `URL Protocol` value of a class key.
+    pub fn protocol(self, key: &str, value_name: &str) -> Option<Protocol> {
+        if !value_name.eq_ignore_ascii_case(URL_PROTOCOL) {
+            return None;
+        }
+
+        let scheme = self.classes_key(key)?;
+
+        // A URI scheme starts with a letter followed by letters, digits, '+', '-', or '.'
+        let mut chars = scheme.chars();
+        if !chars.next().is_some_and(|char| char.is_ascii_alphabetic())
+            || !chars.all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '-' | '.'))
+        {
+            return None;
+        }
+
+        Protocol::new(scheme.to_ascii_lowercase()).ok()
+    }
+}
+
+/// Returns `true` if a key contains a variable that is only resolved at install time, such as an
+/// MSI `[Property]` or an NSIS `$variable`.
+fn is_unresolved(value: &str) -> bool {
+    value.contains(['[', ']', '$', '%'])
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use winget_types::installer::{FileExtension, Protocol};
+
+    use super::RegistryRoot;
+
+    #[rstest]
+    #[case::classes_root(RegistryRoot::ClassesRoot, ".txt", Some("txt"))]
+    #[case::subkey(RegistryRoot::ClassesRoot, r".MD\OpenWithProgids", Some("md"))]
+    #[case::current_user(RegistryRoot::CurrentUser, r"Software\Classes\.log", Some("log"))]
+    #[case::local_machine(RegistryRoot::LocalMachine, r"SOFTWARE\Classes\.json", Some("json"))]
+    #[case::not_classes(RegistryRoot::LocalMachine, r"Software\Example\.txt", None)]
+    #[case::prog_id(RegistryRoot::ClassesRoot, r"Example.Document\shell\open", None)]
+    #[case::property(RegistryRoot::ClassesRoot, ".[EXTENSION]", None)]
+    #[case::users(RegistryRoot::Users, r"Software\Classes\.txt", None)]
+    fn file_extension(
+        #[case] root: RegistryRoot,
+        #[case] key: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            root.file_extension(key),
+            expected.map(|extension| FileExtension::new(extension).unwrap())
+        );
+    }
+
+    #[rstest]
+    #[case::classes_root(RegistryRoot::ClassesRoot, "example", "URL Protocol", Some("example"))]
+    #[case::current_user(
+        RegistryRoot::CurrentUser,
+        r"Software\Classes\Example-App",
+        "url protocol",
+        Some("example-app")
+    )]
+    #[case::other_value(RegistryRoot::ClassesRoot, "example", "", None)]
+    #[case::subkey(RegistryRoot::ClassesRoot, r"example\shell", "URL Protocol", None)]
+    #[case::variable(RegistryRoot::ClassesRoot, "$PROTOCOL", "URL Protocol", None)]
+    fn protocol(
+        #[case] root: RegistryRoot,
+        #[case] key: &str,
+        #[case] value_name: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            root.protocol(key, value_name),
+            expected.map(|protocol| Protocol::new(protocol).unwrap())
+        );
+    }
+}
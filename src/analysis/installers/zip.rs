@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     io,
     io::{Read, Seek, SeekFrom},
     mem,
@@ -10,18 +10,38 @@
 use inquire::{CustomType, MultiSelect, min_length};
 use tracing::debug;
 use winget_types::installer::{
-    Installer, InstallerType, NestedInstallerFiles, PortableCommandAlias,
+    Architecture, Installer, InstallerType, NestedInstallerFiles, PortableCommandAlias,
 };
 use zip::ZipArchive;
 
 use super::super::Analyzer;
-use crate::prompts::handle_inquire_error;
+use crate::{
+    analysis::explain::{ARCHITECTURE, explain},
+    prompts::handle_inquire_error,
+};
 
 const VALID_NESTED_FILE_EXTENSIONS: [&str; 6] =
     ["msix", "msi", "appx", "exe", "msixbundle", "appxbundle"];
 
 const IGNORABLE_FOLDERS: [&str; 2] = ["__MACOSX", "resources"];
 
+const ARCHITECTURES: [(&str, Architecture); 14] = [
+    ("x64", Architecture::X64),
+    ("amd64", Architecture::X64),
+    ("win64", Architecture::X64),
+    ("64bit", Architecture::X64),
+    ("x86", Architecture::X86),
+    ("win32", Architecture::X86),
+    ("ia32", Architecture::X86),
+    ("i386", Architecture::X86),
+    ("i686", Architecture::X86),
+    ("32bit", Architecture::X86),
+    ("arm64", Architecture::Arm64),
+    ("aarch64", Architecture::Arm64),
+    ("arm", Architecture::Arm),
+    ("armv7", Architecture::Arm),
+];
+
 pub struct Zip<R: Read + Seek> {
     archive: ZipArchive<R>,
     pub possible_installer_files: Vec<Utf8PathBuf>,
@@ -75,6 +95,34 @@ pub fn new(reader: R) -> Result<Self> {
         let mut nested_installer_files = BTreeSet::new();
         let mut installers = None;
 
+        if let Some(architecture_files) = Self::architecture_files(&possible_installer_files) {
+            // Archives with a build for each architecture get one installer per architecture
+            debug!(?architecture_files);
+            let mut architecture_installers = Vec::new();
+            for (architecture, file_name) in architecture_files {
+                explain(
+                    ARCHITECTURE,
+                    architecture,
+                    "ZIP",
+                    format_args!("the path of the nested file is {file_name}"),
+                );
+                architecture_installers.extend(
+                    Self::analyze_nested(&mut zip, file_name)?
+                        .into_iter()
+                        .map(|installer| Installer {
+                            architecture,
+                            ..installer
+                        }),
+                );
+            }
+            return Ok(Self {
+                archive: zip,
+                // The nested files have already been resolved, so there is nothing to prompt for
+                possible_installer_files: Vec::new(),
+                installers: architecture_installers,
+            });
+        }
+
         // If there's only one valid file in the zip, extract and analyze it
         if installer_type_counts
             .values()
@@ -87,25 +135,8 @@ pub fn new(reader: R) -> Result<Self> {
                 relative_file_path: chosen_file_name.clone(),
                 portable_command_alias: None,
             }]);
-            if let Ok(mut chosen_file) = zip.by_name(chosen_file_name.as_str()) {
-                let mut temp_file = tempfile::tempfile()?;
-                io::copy(&mut chosen_file, &mut temp_file)?;
-                temp_file.seek(SeekFrom::Start(0))?;
-                let file_analyzer = Analyzer::new(&mut temp_file, chosen_file_name.as_str())?;
-                installers = Some(
-                    file_analyzer
-                        .installers
-                        .into_iter()
-                        .map(|installer| Installer {
-                            r#type: Some(InstallerType::Zip),
-                            nested_installer_type: installer
-                                .r#type
-                                .and_then(|installer_type| installer_type.try_into().ok()),
-                            nested_installer_files: nested_installer_files.clone(),
-                            ..installer
-                        })
-                        .collect::<Vec<_>>(),
-                );
+            if zip.index_for_name(chosen_file_name.as_str()).is_some() {
+                installers = Some(Self::analyze_nested(&mut zip, chosen_file_name)?);
             }
         }
 
@@ -122,6 +153,76 @@ pub fn new(reader: R) -> Result<Self> {
         })
     }
 
+    /// Extracts a nested file from the archive and analyzes it, returning installers that point
+    /// to it as their nested installer.
+    fn analyze_nested(zip: &mut ZipArchive<R>, file_name: &Utf8Path) -> Result<Vec<Installer>> {
+        let mut temp_file = tempfile::tempfile()?;
+        io::copy(&mut zip.by_name(file_name.as_str())?, &mut temp_file)?;
+        temp_file.seek(SeekFrom::Start(0))?;
+        let file_analyzer = Analyzer::new(&mut temp_file, file_name.as_str())?;
+        let nested_installer_files = BTreeSet::from([NestedInstallerFiles {
+            relative_file_path: file_name.to_path_buf(),
+            portable_command_alias: None,
+        }]);
+        Ok(file_analyzer
+            .installers
+            .into_iter()
+            .map(|installer| Installer {
+                r#type: Some(InstallerType::Zip),
+                nested_installer_type: installer
+                    .r#type
+                    .and_then(|installer_type| installer_type.try_into().ok()),
+                nested_installer_files: nested_installer_files.clone(),
+                ..installer
+            })
+            .collect())
+    }
+
+    /// Maps each architecture to its nested file if the archive contains exactly one build per
+    /// architecture, such as `x64/tool.exe` and `arm64/tool.exe`, or `tool-x64.exe` and
+    /// `tool-arm64.exe`.
+    ///
+    /// Returns `None` if any candidate's architecture cannot be determined, if two candidates
+    /// share an architecture, or if there is only one architecture.
+    fn architecture_files(
+        possible_installer_files: &[Utf8PathBuf],
+    ) -> Option<BTreeMap<Architecture, &Utf8Path>> {
+        let mut architecture_files = BTreeMap::new();
+
+        for file_name in possible_installer_files {
+            let architecture = Self::architecture(file_name)?;
+            if architecture_files
+                .insert(architecture, file_name.as_path())
+                .is_some()
+            {
+                return None;
+            }
+        }
+
+        (architecture_files.len() > 1).then_some(architecture_files)
+    }
+
+    /// Determines the architecture of a nested file from its name, or from the name of the
+    /// closest folder it is in.
+    fn architecture(path: &Utf8Path) -> Option<Architecture> {
+        path.file_stem()
+            .into_iter()
+            .chain(path.parent()?.iter().rev())
+            .find_map(|component| {
+                let component = component
+                    .to_ascii_lowercase()
+                    .replace("x86_64", "x64")
+                    .replace("x86-64", "x64");
+
+                component.rsplit(['-', '_', '.', ' ']).find_map(|part| {
+                    ARCHITECTURES
+                        .iter()
+                        .find(|(name, _)| *name == part)
+                        .map(|&(_, architecture)| architecture)
+                })
+            })
+    }
+
     pub fn prompt(&mut self) -> Result<()> {
         if !&self.possible_installer_files.is_empty() {
             let chosen = MultiSelect::new(
@@ -173,3 +274,51 @@ pub fn prompt(&mut self) -> Result<()> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use camino::{Utf8Path, Utf8PathBuf};
+    use rstest::rstest;
+    use winget_types::installer::Architecture;
+
+    use super::Zip;
+
+    type TestZip = Zip<Cursor<Vec<u8>>>;
+
+    #[rstest]
+    #[case::folder("x64/tool.exe", Some(Architecture::X64))]
+    #[case::nested_folder("tool/arm64/bin/tool.exe", Some(Architecture::Arm64))]
+    #[case::suffix("tool-aarch64.exe", Some(Architecture::Arm64))]
+    #[case::x86_64("tool_x86_64.exe", Some(Architecture::X64))]
+    #[case::case_insensitive("Win32/Tool.exe", Some(Architecture::X86))]
+    #[case::file_name_first("x64/tool-arm64.exe", Some(Architecture::Arm64))]
+    #[case::substring("armory/tool.exe", None)]
+    #[case::none("bin/tool.exe", None)]
+    fn architecture(#[case] path: &str, #[case] expected: Option<Architecture>) {
+        assert_eq!(TestZip::architecture(Utf8Path::new(path)), expected);
+    }
+
+    #[test]
+    fn architecture_files() {
+        let files = ["x64/tool.exe", "x86/tool.exe", "arm64/tool.exe"].map(Utf8PathBuf::from);
+
+        let architecture_files = TestZip::architecture_files(&files).unwrap();
+        assert_eq!(architecture_files.len(), 3);
+        assert_eq!(
+            architecture_files[&Architecture::Arm64],
+            Utf8Path::new("arm64/tool.exe")
+        );
+    }
+
+    #[rstest]
+    #[case::single_architecture(&["x64/tool.exe"])]
+    #[case::duplicate_architecture(&["x64/tool.exe", "x64/helper.exe", "arm64/tool.exe"])]
+    #[case::unknown_architecture(&["x64/tool.exe", "arm64/tool.exe", "setup.exe"])]
+    fn no_architecture_files(#[case] files: &[&str]) {
+        let files = files.iter().map(Utf8PathBuf::from).collect::<Vec<_>>();
+
+        assert!(TestZip::architecture_files(&files).is_none());
+    }
+}
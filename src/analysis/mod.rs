@@ -1,7 +1,10 @@
 mod analyzer;
+pub mod explain;
 mod extensions;
+mod file_type;
 pub mod installers;
 mod r#trait;
 
 pub use analyzer::Analyzer;
+pub use file_type::FileType;
 pub use r#trait::Installers;
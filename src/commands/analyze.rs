@@ -1,17 +1,33 @@
 use std::{
+    fmt::Write as _,
     fs::File,
     io,
-    io::{Read, Seek, SeekFrom},
+    io::{Read, Seek, SeekFrom, Write as _},
 };
 
 use anstream::stdout;
 use camino::{Utf8Path, Utf8PathBuf};
-use clap::Parser;
+use chrono::{DateTime, Utc};
+use clap::{Parser, ValueEnum};
 use color_eyre::{Result, eyre::ensure};
+use serde::Serialize;
 use sha2::{Digest, Sha256, digest::Output};
-use winget_types::Sha256String;
+use tracing::warn;
+use winget_types::{
+    Sha256String,
+    installer::Installer,
+    locale::{Copyright, PackageName, Publisher},
+};
 
-use crate::{analysis::Analyzer, manifests::print_manifest};
+use crate::{
+    analysis::{
+        Analyzer,
+        explain::{self, Explanation},
+        installers::pe::{Authenticode, PE, authenticode::DigestAlgorithm},
+    },
+    manifests::print_manifest,
+    read::MappedReader,
+};
 
 /// Analyzes a file and outputs information about it
 #[derive(Parser)]
@@ -39,33 +55,205 @@ pub struct Analyze {
     /// Skip hashing the file
     #[arg(long = "no-hash", alias = "no-sha256")]
     _no_hash: bool,
+
+    /// Explain which parser and heuristic determined each detected field
+    #[arg(long)]
+    explain: bool,
+
+    /// The format to output the analysis in
+    #[arg(long, value_enum, default_value_t = OutputFormat::Yaml)]
+    format: OutputFormat,
+
+    /// Also output the detected publisher, package name, copyright, installer framework and
+    /// signature
+    #[arg(long)]
+    full: bool,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum OutputFormat {
+    Yaml,
+    Json,
+}
+
+/// The result of analyzing a file, as output with `--full` or in JSON.
+///
+/// Fields that were not asked for are left out, but `Installers` is always an array so that JSON
+/// output has the same shape regardless of the flags or the number of installers.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Analysis<'analysis> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    publisher: Option<&'analysis Publisher>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package_name: Option<&'analysis PackageName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    copyright: Option<&'analysis Copyright>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    framework: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<Signature<'analysis>>,
+    installers: &'analysis [Installer],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    explanations: &'analysis [Explanation],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Signature<'analysis> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    common_name: Option<&'analysis str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    organization: Option<&'analysis str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signing_time: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    digest_algorithm: Option<&'static str>,
+}
+
+impl<'analysis> From<&'analysis Authenticode> for Signature<'analysis> {
+    fn from(authenticode: &'analysis Authenticode) -> Self {
+        Self {
+            common_name: authenticode.signer.common_name.as_deref(),
+            organization: authenticode.signer.organization.as_deref(),
+            signing_time: authenticode.signing_time,
+            digest_algorithm: authenticode.digest_algorithm.map(DigestAlgorithm::as_str),
+        }
+    }
 }
 
 impl Analyze {
     pub fn run(self) -> Result<()> {
-        let mut file = File::open(&self.file_path)?;
+        let mut reader = MappedReader::new(File::open(&self.file_path)?);
         let file_name = self
             .file_path
             .file_name()
             .unwrap_or_else(|| self.file_path.as_str());
-        let mut installers = Analyzer::new(&mut file, file_name)?.installers;
+        let (analyzer, explanations) = if self.explain {
+            explain::record(|| Analyzer::new(&mut reader, file_name))
+        } else {
+            (Analyzer::new(&mut reader, file_name), Vec::new())
+        };
+        let Analyzer {
+            publisher,
+            package_name,
+            copyright,
+            framework,
+            mut installers,
+            authenticode,
+            ..
+        } = analyzer?;
+        if let Some(authenticode) = &authenticode {
+            verify_digest(&mut reader, authenticode)?;
+        }
         if self.hash {
-            file.seek(SeekFrom::Start(0))?;
-            let sha_256 = Sha256String::from_digest(&sha256_digest(file)?);
+            reader.seek(SeekFrom::Start(0))?;
+            let sha_256 = Sha256String::from_digest(&sha256_digest(reader)?);
             for installer in &mut installers {
                 installer.sha_256 = sha_256.clone();
             }
         }
-        let yaml = match installers.as_slice() {
-            [installer] => serde_yaml::to_string(installer)?,
-            installers => serde_yaml::to_string(installers)?,
+
+        let analysis = Analysis {
+            publisher: publisher.as_ref().filter(|_| self.full),
+            package_name: package_name.as_ref().filter(|_| self.full),
+            copyright: copyright.as_ref().filter(|_| self.full),
+            framework: framework.filter(|_| self.full),
+            signature: authenticode
+                .as_ref()
+                .filter(|_| self.full)
+                .map(Signature::from),
+            installers: &installers,
+            explanations: &explanations,
         };
+
         let mut lock = stdout().lock();
-        print_manifest(&mut lock, &yaml);
+        match self.format {
+            OutputFormat::Yaml => {
+                let yaml = if self.full {
+                    serde_yaml::to_string(&analysis)?
+                } else {
+                    let mut yaml = match installers.as_slice() {
+                        [installer] => serde_yaml::to_string(installer)?,
+                        installers => serde_yaml::to_string(installers)?,
+                    };
+                    if let Some(signature) = authenticode.as_ref().map(signature_comment) {
+                        yaml.insert_str(0, &signature);
+                    }
+                    if self.explain {
+                        yaml.push_str(&explanation_comment(&explanations));
+                    }
+                    yaml
+                };
+                print_manifest(&mut lock, &yaml);
+            }
+            OutputFormat::Json => {
+                writeln!(lock, "{}", serde_json::to_string_pretty(&analysis)?)?;
+            }
+        }
         Ok(())
     }
 }
 
+/// Warns if the Authenticode digest of a signed file does not match its contents.
+///
+/// This hashes the entire image, so it is only done when analyzing a single file rather than for
+/// every installer downloaded by `new` or `update`.
+fn verify_digest<R: Read + Seek>(mut reader: R, authenticode: &Authenticode) -> Result<()> {
+    reader.seek(SeekFrom::Start(0))?;
+    let pe = PE::read_from(&mut reader)?;
+
+    match authenticode.matches_image(&mut reader, &pe) {
+        Ok(Some(false)) => warn!(
+            "The Authenticode digest does not match the contents of the file. It may have been tampered with or not fully downloaded"
+        ),
+        Ok(_) => {}
+        Err(error) => warn!(%error, "Failed to verify the Authenticode digest"),
+    }
+
+    Ok(())
+}
+
+/// Formats the Authenticode signature of a file as YAML comments so that the output remains a
+/// valid manifest.
+fn signature_comment(authenticode: &Authenticode) -> String {
+    let signer = &authenticode.signer;
+    let mut comment = match (&signer.common_name, &signer.organization) {
+        (Some(common_name), Some(organization)) if common_name != organization => {
+            format!("# Signed by: {common_name} ({organization})\n")
+        }
+        (Some(name), _) | (None, Some(name)) => format!("# Signed by: {name}\n"),
+        (None, None) => "# Signed by: Unknown\n".to_owned(),
+    };
+
+    if let Some(signing_time) = authenticode.signing_time {
+        let _ = writeln!(comment, "# Signed on: {signing_time}");
+    }
+
+    if let Some(digest_algorithm) = authenticode.digest_algorithm {
+        let _ = writeln!(comment, "# Digest algorithm: {digest_algorithm}");
+    }
+
+    comment
+}
+
+/// Formats the reasons behind each detected field as YAML comments, in the order that the
+/// analyzers decided them. A field may be explained more than once if a later heuristic overrode
+/// an earlier one.
+fn explanation_comment(explanations: &[Explanation]) -> String {
+    if explanations.is_empty() {
+        return "# No fields were derived from heuristics\n".to_owned();
+    }
+
+    explanations.iter().fold(
+        String::from("# Explanation:\n"),
+        |mut comment, explanation| {
+            let _ = writeln!(comment, "#   {explanation}");
+            comment
+        },
+    )
+}
+
 fn sha256_digest<R: Read>(mut reader: R) -> io::Result<Output<Sha256>> {
     let mut digest = Sha256::new();
     let mut buffer = [0; 1 << 13];
@@ -0,0 +1,40 @@
+use anstream::println;
+use clap::Parser;
+use color_eyre::eyre::Result;
+use indicatif::HumanBytes;
+use owo_colors::OwoColorize;
+
+use crate::{download::DownloadCache, prompts::text::confirm_prompt};
+
+/// Remove everything from the download cache
+#[derive(Parser)]
+pub struct ClearCache {
+    /// Skip the confirmation prompt to clear the cache
+    #[arg(short = 'y', long = "yes")]
+    skip_prompt: bool,
+}
+
+impl ClearCache {
+    pub fn run(self) -> Result<()> {
+        let cache = DownloadCache::from_env()?;
+
+        let confirm = self.skip_prompt
+            || confirm_prompt(&format!(
+                "Would you like to remove everything in {}?",
+                cache.root()
+            ))?;
+
+        if confirm {
+            let freed = cache.clear()?;
+            println!(
+                "{} cleared the download cache, freeing {}",
+                "Successfully".green(),
+                HumanBytes(freed).blue()
+            );
+        } else {
+            println!("{}", "The download cache was not cleared".cyan());
+        }
+
+        Ok(())
+    }
+}
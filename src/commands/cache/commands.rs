@@ -0,0 +1,16 @@
+use clap::{Args, Subcommand};
+
+use crate::commands::cache::{clear::ClearCache, list::ListCache, prune::PruneCache};
+
+/// Manage the download cache
+#[derive(Args)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheCommands,
+}
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    List(ListCache),
+    Prune(PruneCache),
+    Clear(ClearCache),
+}
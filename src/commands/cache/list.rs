@@ -0,0 +1,42 @@
+use anstream::println;
+use clap::Parser;
+use color_eyre::eyre::Result;
+use indicatif::HumanBytes;
+use owo_colors::OwoColorize;
+
+use crate::download::DownloadCache;
+
+/// List the installers in the download cache
+#[derive(Parser)]
+#[clap(visible_alias = "ls")]
+pub struct ListCache;
+
+impl ListCache {
+    pub fn run(self) -> Result<()> {
+        let cache = DownloadCache::from_env()?;
+        let entries = cache.entries()?;
+
+        if entries.is_empty() {
+            println!("The download cache at {} is empty", cache.root().blue());
+            return Ok(());
+        }
+
+        for entry in &entries {
+            println!(
+                "{} {} {} {}",
+                entry.last_used.format("%Y-%m-%d").blue(),
+                HumanBytes(entry.size).green(),
+                entry.url,
+                entry.sha_256.as_str().dimmed()
+            );
+        }
+
+        println!(
+            "{} cached installers in {}",
+            entries.len(),
+            cache.root().blue()
+        );
+
+        Ok(())
+    }
+}
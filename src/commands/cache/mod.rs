@@ -0,0 +1,4 @@
+pub mod clear;
+pub mod commands;
+pub mod list;
+pub mod prune;
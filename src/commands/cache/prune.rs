@@ -0,0 +1,33 @@
+use anstream::println;
+use chrono::TimeDelta;
+use clap::Parser;
+use color_eyre::eyre::Result;
+use indicatif::HumanBytes;
+use owo_colors::OwoColorize;
+
+use crate::download::DownloadCache;
+
+/// Remove installers from the download cache that have not been used recently
+#[derive(Parser)]
+pub struct PruneCache {
+    /// Remove installers that have not been used for this many days
+    #[arg(long, default_value_t = 30)]
+    days: u32,
+}
+
+impl PruneCache {
+    pub fn run(self) -> Result<()> {
+        let cache = DownloadCache::from_env()?;
+        let pruned = cache.prune(TimeDelta::days(i64::from(self.days)))?;
+
+        println!(
+            "{} {} entries and {} installers, freeing {}",
+            "Removed".green(),
+            pruned.entries,
+            pruned.blobs,
+            HumanBytes(pruned.bytes).blue()
+        );
+
+        Ok(())
+    }
+}
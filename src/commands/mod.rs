@@ -1,4 +1,5 @@
 pub mod analyze;
+pub mod cache;
 pub mod cleanup;
 pub mod complete;
 pub mod list_versions;
@@ -6,6 +7,7 @@
 pub mod remove_dead_versions;
 pub mod remove_version;
 pub mod show_version;
+pub mod status;
 pub mod submit;
 pub mod sync_fork;
 pub mod token;
@@ -13,6 +15,7 @@
 pub mod utils;
 
 use analyze::Analyze;
+use cache::commands::{CacheArgs, CacheCommands};
 use clap::Subcommand;
 use cleanup::Cleanup;
 use complete::Complete;
@@ -21,11 +24,14 @@
 use remove_dead_versions::RemoveDeadVersions;
 use remove_version::RemoveVersion;
 use show_version::ShowVersion;
+use status::Status;
 use submit::Submit;
 use sync_fork::SyncFork;
 use token::commands::{TokenArgs, TokenCommands};
 use update_version::UpdateVersion;
 
+use crate::config::Config;
+
 #[derive(Subcommand)]
 pub enum Commands {
     New(Box<NewVersion>),       // Comparatively large so boxed to store on the heap
@@ -38,15 +44,23 @@ pub enum Commands {
     Sync(SyncFork),
     Complete(Complete),
     Analyze(Analyze),
-    RemoveDeadVersions(RemoveDeadVersions),
+    RemoveDeadVersions(Box<RemoveDeadVersions>), // Comparatively large so boxed to store on the heap
     Submit(Submit),
+    Status(Status),
+    Cache(CacheArgs),
 }
 
 impl Commands {
-    pub async fn run(self) -> color_eyre::Result<()> {
+    /// Returns whether the command uses the upstream, HTTP or forge settings of the configuration
+    /// file, so that commands which don't are not broken by a malformed one.
+    pub const fn uses_config(&self) -> bool {
+        !matches!(self, Self::Complete(_) | Self::Analyze(_) | Self::Cache(_))
+    }
+
+    pub async fn run(self, config: &Config) -> color_eyre::Result<()> {
         match self {
-            Self::New(new_version) => new_version.run().await,
-            Self::Update(update_version) => update_version.run().await,
+            Self::New(new_version) => new_version.run(config).await,
+            Self::Update(update_version) => update_version.run(config).await,
             Self::Cleanup(cleanup) => cleanup.run().await,
             Self::Remove(remove_version) => remove_version.run().await,
             Self::Token(token_args) => match token_args.command {
@@ -58,8 +72,16 @@ pub async fn run(self) -> color_eyre::Result<()> {
             Self::Sync(sync_fork) => sync_fork.run().await,
             Self::Complete(complete) => complete.run(),
             Self::Analyze(analyse) => analyse.run(),
-            Self::RemoveDeadVersions(remove_dead_versions) => remove_dead_versions.run().await,
+            Self::RemoveDeadVersions(remove_dead_versions) => {
+                remove_dead_versions.run(config).await
+            }
             Self::Submit(submit) => submit.run().await,
+            Self::Status(status) => status.run().await,
+            Self::Cache(cache_args) => match cache_args.command {
+                CacheCommands::List(list_cache) => list_cache.run(),
+                CacheCommands::Prune(prune_cache) => prune_cache.run(),
+                CacheCommands::Clear(clear_cache) => clear_cache.run(),
+            },
         }
     }
 }
@@ -13,6 +13,7 @@
 use ordinal::Ordinal;
 use owo_colors::OwoColorize;
 use secrecy::SecretString;
+use tracing::warn;
 use winget_types::{
     LanguageTag, PackageIdentifier, PackageVersion, VersionManifest,
     installer::{
@@ -31,13 +32,11 @@
 };
 
 use crate::{
-    commands::utils::{SPINNER_TICK_RATE, SubmitOption},
-    download::Downloader,
-    github::{
-        GITHUB_HOST,
-        client::GitHub,
-        utils::{PackagePath, pull_request::Change},
-    },
+    commands::utils::{HttpArgs, LocalRepoArgs, SPINNER_TICK_RATE, SubmitOption, SubmitTarget},
+    config::Config,
+    download::{DownloadCache, Downloader, LocalInstaller, open_local},
+    forge::Forges,
+    github::utils::{PackagePath, pull_request::Change},
     manifests::{Manifests, Url, print_changes},
     prompts::{
         check_prompt, handle_inquire_error,
@@ -45,7 +44,6 @@
         radio_prompt,
         text::{confirm_prompt, optional_prompt, required_prompt},
     },
-    token::TokenManager,
 };
 
 /// Create a new package from scratch
@@ -61,9 +59,18 @@ pub struct NewVersion {
     version: Option<PackageVersion>,
 
     /// The list of package installers
+    ///
+    /// Detected values can be overridden per URL, such as
+    /// `https://example.com/setup.exe|x64|scope=user|type=portable`. The supported overrides are
+    /// `arch`, `scope`, `type`, `nested`, `locale` and `custom`.
     #[arg(short, long, num_args = 1.., value_hint = clap::ValueHint::Url)]
     urls: Vec<Url>,
 
+    /// Installers on disk to analyze instead of downloading, paired with the URL they will be
+    /// published at
+    #[arg(long = "installer", value_name = "PATH=URL", value_hint = clap::ValueHint::FilePath)]
+    installers: Vec<LocalInstaller>,
+
     #[arg(long)]
     package_locale: Option<LanguageTag>,
 
@@ -113,6 +120,13 @@ pub struct NewVersion {
     #[arg(long, default_value_t = NonZeroUsize::new(num_cpus::get()).unwrap())]
     concurrent_downloads: NonZeroUsize,
 
+    /// Keep downloaded installers in an on-disk cache, reusing them if they have not changed
+    #[arg(long, env = "KOMAC_CACHE")]
+    cache: bool,
+
+    #[command(flatten)]
+    http: HttpArgs,
+
     /// List of issues that adding this package or version would resolve
     #[arg(long)]
     resolves: Vec<NonZeroU32>,
@@ -145,19 +159,24 @@ pub struct NewVersion {
     #[arg(long, env)]
     skip_pr_check: bool,
 
+    #[command(flatten)]
+    local_repo: LocalRepoArgs,
+
     /// GitHub personal access token with the `public_repo` scope
     #[arg(short, long, env = "GITHUB_TOKEN", hide_env_values = true)]
     token: Option<SecretString>,
 }
 
 impl NewVersion {
-    pub async fn run(self) -> Result<()> {
-        let token_manager = TokenManager::handle(self.token).await?;
-        let github = GitHub::new(token_manager)?;
+    pub async fn run(self, config: &Config) -> Result<()> {
+        let target = self.local_repo.target(self.token).await?;
 
         let identifier = required_prompt(self.identifier, None::<&str>)?;
 
-        let package = github.get_package(&identifier).await?;
+        let package = match &target {
+            SubmitTarget::Local(local_repo) => local_repo.get_package(&identifier)?,
+            SubmitTarget::GitHub(github) => github.get_package(&identifier).await?,
+        };
 
         if let Some(latest_version) = package.latest_version() {
             println!("Latest version of {identifier}: {latest_version}");
@@ -165,13 +184,16 @@ pub async fn run(self) -> Result<()> {
 
         let version = required_prompt(self.version, None::<&str>)?;
 
-        let mut package = package.into_versioned(&version, &github).await?;
+        let mut package = match &target {
+            SubmitTarget::Local(_) => package.with_version(&version),
+            SubmitTarget::GitHub(github) => package.into_versioned(&version, github).await?,
+        };
         if !self.skip_pr_check && !self.dry_run && !package.prompt_existing_pr()? {
             return Ok(());
         }
 
         let mut urls = self.urls;
-        if urls.is_empty() {
+        if urls.is_empty() && self.installers.is_empty() {
             while urls.len() < 1024 {
                 let message = format!("{} Installer URL", Ordinal(urls.len() + 1));
                 let url_prompt =
@@ -192,22 +214,31 @@ pub async fn run(self) -> Result<()> {
             }
         }
 
+        let http_config = self.http.config(&config.http);
         let github_values = tokio::spawn({
-            let github = github.clone();
-            let github_url = urls
+            let forges = Forges::new(target.github().cloned(), &http_config, &config.forges)?;
+            let release_urls = urls
                 .iter()
-                .find(|url| url.host_str() == Some(GITHUB_HOST))
-                .cloned();
+                .chain(self.installers.iter().map(LocalInstaller::url))
+                .map(|url| url.clone().into_inner())
+                .collect::<Vec<_>>();
             async move {
-                github_url
-                    .map(|url| github.get_all_values_from_url(url.into_inner()))
-                    .unwrap_or_default()
+                // The release values are optional, so an unreachable forge should not stop the
+                // new version
+                forges
+                    .get_release_values(&release_urls)
                     .await
+                    .unwrap_or_else(|error| {
+                        warn!(%error, "Failed to get values from the release");
+                        None
+                    })
             }
         });
 
-        let downloader = Downloader::new_with_concurrent(self.concurrent_downloads)?;
+        let downloader = Downloader::new_with_concurrent(self.concurrent_downloads, &http_config)?
+            .with_cache(self.cache.then(DownloadCache::from_env).transpose()?);
         let mut files = downloader.download(urls.iter().cloned()).await?;
+        files.extend(open_local(self.installers).await?);
         let mut download_results = files.analyze().await?;
 
         let mut installers = Vec::new();
@@ -290,10 +321,7 @@ pub async fn run(self) -> Result<()> {
             ..InstallerManifest::default()
         };
 
-        let mut github_values = match github_values.await? {
-            Some(future) => Some(future?),
-            None => None,
-        };
+        let mut github_values = github_values.await?;
 
         let default_locale_manifest = DefaultLocaleManifest {
             package_identifier: identifier.clone(),
@@ -422,6 +450,23 @@ pub async fn run(self) -> Result<()> {
             return Ok(());
         }
 
+        let github = match target {
+            SubmitTarget::Local(local_repo) => {
+                local_repo
+                    .add_version(
+                        &identifier,
+                        &version,
+                        Some(package.versions()),
+                        &changes,
+                        None,
+                        self.local_repo.push(),
+                    )?
+                    .print_success();
+                return Ok(());
+            }
+            SubmitTarget::GitHub(github) => github,
+        };
+
         // Create an indeterminate progress bar to show as a pull request is being created
         let pr_progress = ProgressBar::new_spinner().with_message(format!(
             "Creating a pull request for {identifier} {version}"
@@ -437,6 +482,7 @@ pub async fn run(self) -> Result<()> {
             .issue_resolves(&self.resolves)
             .maybe_created_with(self.created_with.as_deref())
             .maybe_created_with_url(self.created_with_url.as_ref())
+            .progress(&pr_progress)
             .send()
             .await?;
 
@@ -8,7 +8,7 @@
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use reqwest::{Client, StatusCode};
+use reqwest::StatusCode;
 use secrecy::SecretString;
 use tokio::{sync::mpsc, try_join};
 use winget_types::{
@@ -17,7 +17,9 @@
 };
 
 use crate::{
-    commands::utils::{RateLimit, SPINNER_SLOW_TICK_RATE},
+    commands::utils::{HttpArgs, RateLimit, SPINNER_SLOW_TICK_RATE},
+    config::Config,
+    download::HttpClients,
     github::client::GitHub,
     prompts::text::confirm_prompt,
     token::{TokenManager, default_headers},
@@ -59,10 +61,13 @@ pub struct RemoveDeadVersions {
     /// GitHub personal access token with the `public_repo` scope
     #[arg(short, long, env = "GITHUB_TOKEN", hide_env_values = true)]
     token: Option<SecretString>,
+
+    #[command(flatten)]
+    http: HttpArgs,
 }
 
 impl RemoveDeadVersions {
-    pub async fn run(self) -> Result<()> {
+    pub async fn run(self, config: &Config) -> Result<()> {
         let token_manager = TokenManager::handle(self.token).await?;
         let github = GitHub::new(token_manager)?;
 
@@ -74,9 +79,7 @@ pub async fn run(self) -> Result<()> {
             github.get_versions(&self.package_identifier)
         )?;
 
-        let client = Client::builder()
-            .default_headers(default_headers(None))
-            .build()?;
+        let clients = HttpClients::new(&self.http.config(&config.http), &default_headers(None))?;
 
         let versions = versions
             .into_iter()
@@ -166,7 +169,7 @@ pub async fn run(self) -> Result<()> {
                 let package_identifier = &package_identifier;
                 let github = &github;
                 let sender = &sender;
-                let client = &client;
+                let clients = &clients;
                 let overall_progress = &overall_progress;
                 let progress_bar = &progress_bars[index % self.concurrent.get()];
                 async move {
@@ -186,11 +189,10 @@ pub async fn run(self) -> Result<()> {
                         .unique();
 
                     let url_statuses = stream::iter(installer_urls)
-                        .map(|url| {
-                            client
-                                .head((*url).clone())
-                                .send()
-                                .map_ok(|response| (url, response.status()))
+                        .map(|url| async move {
+                            let response =
+                                clients.send(&url, |client, url| client.head(url)).await?;
+                            Ok::<_, reqwest::Error>((url, response.status()))
                         })
                         .buffered(2)
                         .try_collect::<Vec<(_, _)>>()
@@ -14,7 +14,7 @@
 use winget_types::{PackageIdentifier, PackageVersion};
 
 use crate::{
-    github::{WINGET_PKGS_FULL_NAME, client::GitHub},
+    github::{Upstream, client::GitHub},
     prompts::{handle_inquire_error, text::confirm_prompt},
     token::TokenManager,
 };
@@ -81,9 +81,10 @@ pub async fn run(self) -> Result<()> {
 
         if !versions.contains(&self.package_version) {
             bail!(
-                "{} version {} does not exist in {WINGET_PKGS_FULL_NAME}",
+                "{} version {} does not exist in {}",
                 self.package_identifier,
                 self.package_version,
+                Upstream::get(),
             );
         }
 
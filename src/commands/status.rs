@@ -0,0 +1,269 @@
+use std::{fmt::Write as _, io::Write};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use clap::{Parser, ValueEnum};
+use color_eyre::Result;
+use indicatif::ProgressBar;
+use owo_colors::OwoColorize;
+use secrecy::SecretString;
+use serde::Serialize;
+
+use crate::{
+    commands::utils::SPINNER_TICK_RATE,
+    github::{
+        Upstream,
+        client::GitHub,
+        graphql::{
+            get_pull_requests::{Check, PullRequest, StatusState},
+            types::PullRequestState,
+        },
+    },
+    terminal::Hyperlinkable,
+    token::TokenManager,
+};
+
+/// Lists your pull requests to the upstream repository with their labels, checks and age
+#[derive(Parser)]
+#[clap(visible_alias = "prs")]
+pub struct Status {
+    /// Only list pull requests in this state
+    #[arg(long, value_enum, default_value_t = StateFilter::Open)]
+    state: StateFilter,
+
+    /// Only list pull requests that have this label, such as Needs-Author-Feedback
+    #[arg(short, long = "label", value_name = "LABEL")]
+    labels: Vec<String>,
+
+    /// Only list pull requests whose checks are in this state
+    #[arg(long, value_enum)]
+    checks: Option<ChecksFilter>,
+
+    /// List the pull requests of this user instead of the current user
+    #[arg(long)]
+    author: Option<String>,
+
+    /// Output the pull requests as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Output the pull requests as prettified JSON
+    #[arg(long, conflicts_with = "json")]
+    pretty_json: bool,
+
+    /// GitHub personal access token with the `public_repo` scope
+    #[arg(short, long, env = "GITHUB_TOKEN", hide_env_values = true)]
+    token: Option<SecretString>,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum StateFilter {
+    Open,
+    Closed,
+    Merged,
+    All,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum ChecksFilter {
+    Success,
+    Failure,
+    Pending,
+}
+
+impl ChecksFilter {
+    fn matches(self, state: Option<StatusState>) -> bool {
+        match self {
+            Self::Success => state == Some(StatusState::Success),
+            Self::Failure => state.is_some_and(StatusState::is_failure),
+            Self::Pending => state.is_none_or(StatusState::is_pending),
+        }
+    }
+}
+
+/// A pull request as output with `--json`.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct PullRequestStatus<'pr> {
+    number: i32,
+    title: &'pr str,
+    url: &'pr str,
+    state: &'static str,
+    draft: bool,
+    labels: Vec<&'pr str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check_state: Option<&'static str>,
+    checks: Vec<Check<'pr>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl<'pr> From<&'pr PullRequest> for PullRequestStatus<'pr> {
+    fn from(pull_request: &'pr PullRequest) -> Self {
+        Self {
+            number: pull_request.number,
+            title: &pull_request.title,
+            url: pull_request.url.as_str(),
+            state: state_name(pull_request.state),
+            draft: pull_request.is_draft,
+            labels: pull_request.label_names().collect(),
+            check_state: pull_request.check_state().map(StatusState::as_str),
+            checks: pull_request.checks().collect(),
+            created_at: pull_request.created_at,
+            updated_at: pull_request.updated_at,
+        }
+    }
+}
+
+impl Status {
+    pub async fn run(self) -> Result<()> {
+        let token_manager = TokenManager::handle(self.token).await?;
+        let github = GitHub::new(token_manager)?;
+
+        let pb = ProgressBar::new_spinner().with_message("Retrieving pull requests");
+        pb.enable_steady_tick(SPINNER_TICK_RATE);
+
+        let author = match &self.author {
+            Some(author) => author.clone(),
+            None => github.get_username().await?,
+        };
+
+        let pull_requests = github
+            .get_pull_requests(&search_query(&author, self.state, &self.labels))
+            .await?
+            .into_iter()
+            .filter(|pull_request| {
+                self.checks
+                    .is_none_or(|checks| checks.matches(pull_request.check_state()))
+            })
+            .collect::<Vec<_>>();
+
+        pb.finish_and_clear();
+
+        let mut stdout = anstream::stdout().lock();
+
+        if self.json || self.pretty_json {
+            let statuses = pull_requests
+                .iter()
+                .map(PullRequestStatus::from)
+                .collect::<Vec<_>>();
+            if self.pretty_json {
+                serde_json::to_writer_pretty(&mut stdout, &statuses)?;
+            } else {
+                serde_json::to_writer(&mut stdout, &statuses)?;
+            }
+            return Ok(());
+        }
+
+        if pull_requests.is_empty() {
+            writeln!(stdout, "There are no matching pull requests by {author}")?;
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        for pull_request in &pull_requests {
+            let number = format!("#{}", pull_request.number);
+            write!(
+                stdout,
+                "{} {} {}",
+                number.hyperlink(&pull_request.url).blue(),
+                pull_request.title,
+                format_args!(
+                    "({}, {})",
+                    format_age(now - pull_request.created_at),
+                    state_name(pull_request.state)
+                )
+                .dimmed()
+            )?;
+
+            match pull_request.check_state() {
+                Some(state) if state.is_failure() => {
+                    write!(stdout, " checks {}", state.as_str().red())?;
+                }
+                Some(StatusState::Success) => {
+                    write!(stdout, " checks {}", StatusState::Success.as_str().green())?;
+                }
+                Some(state) => write!(stdout, " checks {}", state.as_str().yellow())?,
+                None => {}
+            }
+
+            if pull_request.is_draft {
+                write!(stdout, " {}", "draft".dimmed())?;
+            }
+
+            for label in pull_request.label_names() {
+                write!(stdout, " {}", format_args!("[{label}]").purple())?;
+            }
+
+            writeln!(stdout)?;
+        }
+
+        writeln!(
+            stdout,
+            "{} pull {} by {author}",
+            pull_requests.len().blue().bold(),
+            match pull_requests.len() {
+                1 => "request",
+                _ => "requests",
+            }
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Returns the search query for the author's pull requests to the upstream repository.
+fn search_query(author: &str, state: StateFilter, labels: &[String]) -> String {
+    let mut query = format!(
+        "repo:{} is:pr author:{author} sort:created-desc",
+        Upstream::get()
+    );
+
+    query.push_str(match state {
+        StateFilter::Open => " is:open",
+        StateFilter::Closed => " is:closed is:unmerged",
+        StateFilter::Merged => " is:merged",
+        StateFilter::All => "",
+    });
+
+    for label in labels {
+        let _ = write!(query, r#" label:"{label}""#);
+    }
+
+    query
+}
+
+const fn state_name(state: PullRequestState) -> &'static str {
+    match state {
+        PullRequestState::Open => "open",
+        PullRequestState::Closed => "closed",
+        PullRequestState::Merged => "merged",
+    }
+}
+
+/// Formats how long ago something happened in its largest whole unit, such as `3d`.
+fn format_age(age: TimeDelta) -> String {
+    if age.num_days() > 0 {
+        format!("{}d", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h", age.num_hours())
+    } else {
+        format!("{}m", age.num_minutes().max(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+    use rstest::rstest;
+
+    use super::format_age;
+
+    #[rstest]
+    #[case(TimeDelta::days(3) + TimeDelta::hours(5), "3d")]
+    #[case(TimeDelta::hours(5) + TimeDelta::minutes(59), "5h")]
+    #[case(TimeDelta::minutes(12), "12m")]
+    #[case(TimeDelta::seconds(-5), "0m")]
+    fn formats_age(#[case] age: TimeDelta, #[case] expected: &str) {
+        assert_eq!(format_age(age), expected);
+    }
+}
@@ -13,13 +13,12 @@
 use winget_types::{Manifest as WingetManifest, ManifestType, utils::GenericManifest};
 
 use crate::{
-    commands::utils::{RateLimit, SPINNER_TICK_RATE, SubmitOption},
-    github::client::GitHub,
+    commands::utils::{LocalRepoArgs, RateLimit, SPINNER_TICK_RATE, SubmitOption, SubmitTarget},
     manifests::{Manifests, manifest::Manifest},
     prompts::handle_inquire_error,
-    token::TokenManager,
 };
 
+/// Submits existing manifests in a folder to the upstream repository
 #[expect(clippy::struct_excessive_bools, reason = "CLI flags")]
 #[derive(Parser)]
 pub struct Submit {
@@ -50,6 +49,9 @@ pub struct Submit {
     #[arg(long, env = "DRY_RUN")]
     dry_run: bool,
 
+    #[command(flatten)]
+    local_repo: LocalRepoArgs,
+
     /// GitHub personal access token with the `public_repo` scope
     #[arg(short, long, env = "GITHUB_TOKEN", hide_env_values = true)]
     token: Option<SecretString>,
@@ -57,7 +59,7 @@ pub struct Submit {
 
 impl Submit {
     pub async fn run(mut self) -> Result<()> {
-        let token_manager = TokenManager::handle(self.token.take()).await?;
+        let target = self.local_repo.target(self.token.take()).await?;
 
         let yaml_entries = self.get_yaml_file_paths()?;
 
@@ -137,8 +139,6 @@ pub async fn run(mut self) -> Result<()> {
 
         let rate_limit = RateLimit::new(self.fast);
 
-        let github = GitHub::new(token_manager)?;
-
         for mut package_manifests in manifests {
             // Reorder the keys in case the manifests weren't created by komac
             package_manifests.installer.optimize();
@@ -165,6 +165,24 @@ pub async fn run(mut self) -> Result<()> {
                 continue;
             }
 
+            let github = match &target {
+                SubmitTarget::Local(local_repo) => {
+                    let versions = local_repo.get_versions(identifier).ok();
+                    local_repo
+                        .add_version(
+                            identifier,
+                            version,
+                            versions.as_ref(),
+                            &changes,
+                            None,
+                            self.local_repo.push(),
+                        )?
+                        .print_success();
+                    continue;
+                }
+                SubmitTarget::GitHub(github) => github,
+            };
+
             let versions = github.get_versions(identifier).await.ok();
 
             rate_limit.wait().await;
@@ -182,6 +200,7 @@ pub async fn run(mut self) -> Result<()> {
                 .maybe_versions(versions.as_ref())
                 .changes(changes)
                 .issue_resolves(&self.resolves)
+                .progress(&pr_progress)
                 .send()
                 .await?;
 
@@ -11,12 +11,13 @@
 
 use crate::{
     commands::utils::{SPINNER_TICK_RATE, environment::VHS},
-    github::{WINGET_PKGS, WINGET_PKGS_FULL_NAME, client::GitHub},
+    github::{Upstream, client::GitHub},
     terminal::Hyperlinkable,
     token::TokenManager,
 };
 
-/// Merges changes from microsoft/winget-pkgs into the fork repository
+/// Merges changes from the upstream repository, microsoft/winget-pkgs by default, into the fork
+/// repository
 #[derive(Parser)]
 #[clap(visible_aliases = ["sync-fork", "merge-upstream"])]
 pub struct SyncFork {
@@ -94,11 +95,12 @@ pub async fn run(self) -> Result<()> {
     }
 
     async fn vhs() -> Result<()> {
+        let upstream = Upstream::get();
         let merge_message = format!(
             "{} upstream commits from {} into {}",
             random_range(50..=500),
-            WINGET_PKGS_FULL_NAME.blue(),
-            format_args!("octocat/{WINGET_PKGS}").blue()
+            upstream.blue(),
+            format_args!("octocat/{}", upstream.repo()).blue()
         );
 
         let pb = ProgressBar::new_spinner().with_message(format!("Merging {merge_message}"));
@@ -12,6 +12,7 @@
 use owo_colors::OwoColorize;
 use secrecy::SecretString;
 use tokio::try_join;
+use tracing::warn;
 use winget_types::{
     PackageIdentifier, PackageVersion,
     url::{DecodedUrl, ReleaseNotesUrl},
@@ -19,15 +20,15 @@
 
 use crate::{
     analysis::Analyzer,
-    commands::utils::{SPINNER_TICK_RATE, SubmitOption},
-    download::Downloader,
+    commands::utils::{HttpArgs, LocalRepoArgs, SPINNER_TICK_RATE, SubmitOption, SubmitTarget},
+    config::Config,
+    download::{DownloadCache, Downloader, LocalInstaller, open_local},
+    forge::Forges,
     github::{
-        GITHUB_HOST, GitHubError, WINGET_PKGS_FULL_NAME,
-        client::{GitHub, GitHubValues},
+        Upstream,
         utils::{PackagePath, pull_request::Change},
     },
     manifests::{Url, print_changes},
-    token::TokenManager,
 };
 
 /// Add a version to a pre-existing package
@@ -43,13 +44,35 @@ pub struct UpdateVersion {
     version: PackageVersion,
 
     /// The list of package installers
-    #[arg(short, long, num_args = 1.., required = true, value_hint = clap::ValueHint::Url)]
+    ///
+    /// Detected values can be overridden per URL, such as
+    /// `https://example.com/setup.exe|x64|scope=user|type=portable`. The supported overrides are
+    /// `arch`, `scope`, `type`, `nested`, `locale` and `custom`.
+    #[arg(
+        short,
+        long,
+        num_args = 1..,
+        required_unless_present = "installers",
+        value_hint = clap::ValueHint::Url
+    )]
     urls: Vec<Url>,
 
+    /// Installers on disk to analyze instead of downloading, paired with the URL they will be
+    /// published at
+    #[arg(long = "installer", value_name = "PATH=URL", value_hint = clap::ValueHint::FilePath)]
+    installers: Vec<LocalInstaller>,
+
     /// Number of installers to download at the same time
     #[arg(long, default_value_t = NonZeroUsize::new(num_cpus::get()).unwrap())]
     concurrent_downloads: NonZeroUsize,
 
+    /// Keep downloaded installers in an on-disk cache, reusing them if they have not changed
+    #[arg(long, env = "KOMAC_CACHE")]
+    cache: bool,
+
+    #[command(flatten)]
+    http: HttpArgs,
+
     /// List of issues that updating this package would resolve
     #[arg(long)]
     resolves: Vec<NonZeroU32>,
@@ -90,19 +113,33 @@ pub struct UpdateVersion {
     #[arg(long, env)]
     skip_pr_check: bool,
 
+    /// Push the manifests as a new commit to an existing pull request instead of opening a new
+    /// one
+    #[arg(long, value_name = "NUMBER", conflicts_with = "local_repo")]
+    amend_pr: Option<NonZeroU32>,
+
+    #[command(flatten)]
+    local_repo: LocalRepoArgs,
+
     /// GitHub personal access token with the `public_repo` scope
     #[arg(short, long, env = "GITHUB_TOKEN", hide_env_values = true)]
     token: Option<SecretString>,
 }
 
 impl UpdateVersion {
-    pub async fn run(mut self) -> Result<()> {
-        let token_manager = TokenManager::handle(self.token.take()).await?;
-        let github = GitHub::new(&token_manager)?;
-
-        let mut package = github
-            .get_versioned_package(&self.identifier, &self.version)
-            .await?;
+    pub async fn run(mut self, config: &Config) -> Result<()> {
+        let target = self.local_repo.target(self.token.take()).await?;
+
+        let mut package = match &target {
+            SubmitTarget::Local(local_repo) => {
+                local_repo.get_versioned_package(&self.identifier, &self.version)?
+            }
+            SubmitTarget::GitHub(github) => {
+                github
+                    .get_versioned_package(&self.identifier, &self.version)
+                    .await?
+            }
+        };
 
         println!(
             "Latest version of {}: {}",
@@ -110,7 +147,11 @@ pub async fn run(mut self) -> Result<()> {
             package.latest_version()
         );
 
-        if !self.skip_pr_check && !self.dry_run && !package.prompt_existing_pr()? {
+        if !self.skip_pr_check
+            && !self.dry_run
+            && self.amend_pr.is_none()
+            && !package.prompt_existing_pr()?
+        {
             return Ok(());
         }
 
@@ -118,11 +159,28 @@ pub async fn run(mut self) -> Result<()> {
             .resolve_replace_version(package.versions(), package.latest_version())?
             .cloned();
 
-        let downloader = Downloader::new_with_concurrent(self.concurrent_downloads)?;
-        let (mut github_values, mut files) = try_join!(
-            self.fetch_github_values(&github).map_err(Error::new),
+        let http_config = self.http.config(&config.http);
+        let forges = Forges::new(target.github().cloned(), &http_config, &config.forges)?;
+        let downloader = Downloader::new_with_concurrent(self.concurrent_downloads, &http_config)?
+            .with_cache(self.cache.then(DownloadCache::from_env).transpose()?);
+        let (mut github_values, mut files, local_files) = try_join!(
+            forges
+                .get_release_values(
+                    self.urls
+                        .iter()
+                        .chain(self.installers.iter().map(LocalInstaller::url))
+                        .map(|url| &**url),
+                )
+                .or_else(|error| async move {
+                    // The release values are optional, so an unreachable forge should not stop the
+                    // update
+                    warn!(%error, "Failed to get values from the release");
+                    Ok::<_, Error>(None)
+                }),
             downloader.download(self.urls.iter().cloned()),
+            open_local(self.installers.iter().cloned()),
         )?;
+        files.extend(local_files);
 
         let manifests = package.manifests_mut().unwrap();
 
@@ -173,6 +231,50 @@ pub async fn run(mut self) -> Result<()> {
             return Ok(());
         }
 
+        let github = match target {
+            SubmitTarget::Local(local_repo) => {
+                local_repo
+                    .add_version(
+                        &self.identifier,
+                        &self.version,
+                        Some(package.versions()),
+                        &changes,
+                        replace_version.as_ref(),
+                        self.local_repo.push(),
+                    )?
+                    .print_success();
+                return Ok(());
+            }
+            SubmitTarget::GitHub(github) => github,
+        };
+
+        if let Some(number) = self.amend_pr {
+            let pr_progress =
+                ProgressBar::new_spinner().with_message(format!("Amending pull request #{number}"));
+            pr_progress.enable_steady_tick(SPINNER_TICK_RATE);
+
+            let pull_request = github
+                .amend_pull_request()
+                .number(number.get())
+                .identifier(&self.identifier)
+                .version(&self.version)
+                .versions(package.versions())
+                .changes(changes)
+                .maybe_replace_version(replace_version.as_ref())
+                .send()
+                .await?;
+
+            pr_progress.finish_and_clear();
+
+            pull_request.print_success();
+
+            if self.open_pr {
+                open::that(pull_request.url.as_str())?;
+            }
+
+            return Ok(());
+        }
+
         // Create an indeterminate progress bar to show as a pull request is being created
         let pr_progress = ProgressBar::new_spinner().with_message(format!(
             "Creating a pull request for {} {}",
@@ -190,6 +292,7 @@ pub async fn run(mut self) -> Result<()> {
             .issue_resolves(&self.resolves)
             .maybe_created_with(self.created_with.as_deref())
             .maybe_created_with_url(self.created_with_url.as_ref())
+            .progress(&pr_progress)
             .send()
             .await?;
 
@@ -226,28 +329,11 @@ fn resolve_replace_version<'a>(
             && let Some(closest) = version.closest(versions)
         {
             bail!(
-                "Replacement version {version} does not exist in {WINGET_PKGS_FULL_NAME}. The closest version is {closest}"
+                "Replacement version {version} does not exist in {}. The closest version is {closest}",
+                Upstream::get()
             )
         }
 
         Ok(replace_version)
     }
-
-    async fn fetch_github_values(
-        &self,
-        github: &GitHub,
-    ) -> Result<Option<GitHubValues>, GitHubError> {
-        if let Some(url) = self
-            .urls
-            .iter()
-            .find(|url| url.host_str() == Some(GITHUB_HOST))
-        {
-            github
-                .get_all_values_from_url(url.clone().into_inner())
-                .await
-                .transpose()
-        } else {
-            Ok(None)
-        }
-    }
 }
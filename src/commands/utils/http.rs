@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+
+use clap::Args;
+
+use crate::download::{ClientConfig, HttpConfig};
+
+/// Command line overrides for the `http` section of the configuration file.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "HTTP")]
+pub struct HttpArgs {
+    /// User agent to send instead of the default
+    #[arg(long, env = "KOMAC_USER_AGENT")]
+    user_agent: Option<String>,
+
+    /// Extra header to send with every request
+    #[arg(long = "header", value_name = "NAME: VALUE", value_parser = parse_header)]
+    headers: Vec<(String, String)>,
+
+    /// Proxy to send every request through instead of the system proxy
+    #[arg(long, env = "KOMAC_PROXY", value_hint = clap::ValueHint::Url)]
+    proxy: Option<url::Url>,
+
+    /// Seconds to wait for a connection to be established
+    #[arg(long, env = "KOMAC_CONNECT_TIMEOUT", value_name = "SECONDS")]
+    connect_timeout: Option<u64>,
+
+    /// Seconds to wait for each read from a connection
+    #[arg(long, env = "KOMAC_READ_TIMEOUT", value_name = "SECONDS")]
+    read_timeout: Option<u64>,
+}
+
+impl HttpArgs {
+    /// Applies these overrides to the `http` section of the configuration file.
+    pub fn config(&self, config: &HttpConfig) -> HttpConfig {
+        config.clone().with_overrides(ClientConfig {
+            user_agent: self.user_agent.clone(),
+            proxy: self.proxy.clone(),
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+            headers: self.headers.iter().cloned().collect::<BTreeMap<_, _>>(),
+        })
+    }
+}
+
+fn parse_header(header: &str) -> Result<(String, String), &'static str> {
+    header
+        .split_once(':')
+        .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+        .filter(|(name, _)| !name.is_empty())
+        .ok_or("Expected a header as NAME: VALUE")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::parse_header;
+
+    #[rstest]
+    #[case("Cookie: session=abc", Some(("Cookie", "session=abc")))]
+    #[case("Authorization:Bearer abc", Some(("Authorization", "Bearer abc")))]
+    #[case("X-Empty:", Some(("X-Empty", "")))]
+    #[case("Cookie", None)]
+    #[case(": value", None)]
+    fn parse(#[case] header: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(
+            parse_header(header).ok(),
+            expected.map(|(name, value)| (name.to_owned(), value.to_owned()))
+        );
+    }
+}
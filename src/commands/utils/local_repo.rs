@@ -0,0 +1,74 @@
+use camino::Utf8PathBuf;
+use clap::Args;
+use color_eyre::Result;
+use secrecy::SecretString;
+
+use crate::{
+    git::{GitError, LocalRepository},
+    github::client::GitHub,
+    token::TokenManager,
+};
+
+/// Options for committing manifests to a local clone instead of opening a pull request.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "Local repository")]
+pub struct LocalRepoArgs {
+    /// Commit manifests to a new branch in a local clone of the upstream repository instead of
+    /// opening a pull request
+    #[arg(long, env = "KOMAC_LOCAL_REPO", value_name = "PATH", value_hint = clap::ValueHint::DirPath)]
+    local_repo: Option<Utf8PathBuf>,
+
+    /// Push the new branch to a remote after committing
+    #[arg(
+        long,
+        requires = "local_repo",
+        num_args = 0..=1,
+        default_missing_value = "origin",
+        value_name = "REMOTE"
+    )]
+    push: Option<String>,
+}
+
+impl LocalRepoArgs {
+    /// Opens the local repository, if one was given.
+    fn open(&self) -> Result<Option<LocalRepository>, GitError> {
+        self.local_repo
+            .as_ref()
+            .map(LocalRepository::open)
+            .transpose()
+    }
+
+    /// Returns the remote to push new branches to, if any.
+    #[inline]
+    pub fn push(&self) -> Option<&str> {
+        self.push.as_deref()
+    }
+
+    /// Opens the local repository if one was given, or otherwise connects to GitHub with `token`.
+    ///
+    /// A local repository does not need GitHub, so no token is asked for in that case.
+    pub async fn target(&self, token: Option<SecretString>) -> Result<SubmitTarget> {
+        Ok(match self.open()? {
+            Some(local_repo) => SubmitTarget::Local(local_repo),
+            None => SubmitTarget::GitHub(GitHub::new(TokenManager::handle(token).await?)?),
+        })
+    }
+}
+
+/// Where packages are read from and new versions are submitted to.
+pub enum SubmitTarget {
+    /// A local clone, where new versions are committed to branches.
+    Local(LocalRepository),
+    /// The upstream repository on GitHub, where new versions are opened as pull requests.
+    GitHub(GitHub),
+}
+
+impl SubmitTarget {
+    /// Returns the GitHub client, unless committing to a local repository.
+    pub const fn github(&self) -> Option<&GitHub> {
+        match self {
+            Self::Local(_) => None,
+            Self::GitHub(github) => Some(github),
+        }
+    }
+}
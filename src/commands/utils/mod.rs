@@ -1,11 +1,17 @@
 pub mod environment;
+mod http;
+mod local_repo;
 mod rate_limit;
 mod submit_option;
+mod upstream;
 
 use std::time::Duration;
 
+pub use http::HttpArgs;
+pub use local_repo::{LocalRepoArgs, SubmitTarget};
 pub use rate_limit::RateLimit;
 pub use submit_option::SubmitOption;
+pub use upstream::UpstreamArgs;
 
 pub const SPINNER_TICK_RATE: Duration = Duration::from_millis(50);
 
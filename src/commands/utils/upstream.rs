@@ -0,0 +1,37 @@
+use clap::Args;
+
+use crate::github::{Repository, Upstream, UpstreamConfig};
+
+/// Command line overrides for the `upstream` section of the configuration file.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "Upstream")]
+pub struct UpstreamArgs {
+    /// Repository to read manifests from and open pull requests against [default: microsoft/winget-pkgs]
+    #[arg(long, global = true, env = "KOMAC_UPSTREAM", value_name = "OWNER/REPO")]
+    upstream: Option<Repository>,
+
+    /// Branch of the upstream repository to open pull requests against instead of its default branch
+    #[arg(
+        long,
+        global = true,
+        env = "KOMAC_UPSTREAM_BRANCH",
+        value_name = "BRANCH"
+    )]
+    upstream_branch: Option<String>,
+
+    /// Host of the GitHub Enterprise Server instance that hosts the upstream repository
+    #[arg(long, global = true, env = "KOMAC_GITHUB_HOST", value_name = "HOST")]
+    github_host: Option<String>,
+}
+
+impl UpstreamArgs {
+    /// Sets the upstream repository from the configuration file with these overrides applied.
+    pub fn init(&self, config: &UpstreamConfig) {
+        let upstream = Upstream::default().merge(config).merge(&UpstreamConfig {
+            repository: self.upstream.clone(),
+            branch: self.upstream_branch.clone(),
+            host: self.github_host.clone(),
+        });
+        Upstream::init(upstream);
+    }
+}
@@ -0,0 +1,109 @@
+use std::{collections::BTreeMap, env, fs, io};
+
+use camino::Utf8PathBuf;
+use color_eyre::{Result, eyre::WrapErr};
+use serde::Deserialize;
+
+use crate::{dirs::PlatformDir, download::HttpConfig, forge::ForgeKind, github::UpstreamConfig};
+
+/// Komac's configuration file.
+///
+/// This is read from the path set by `KOMAC_CONFIG`, falling back to `komac/config.yaml` in the
+/// platform's per-user configuration directory. A missing file is the same as an empty one.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct Config {
+    pub http: HttpConfig,
+    pub upstream: UpstreamConfig,
+
+    /// Self-hosted forges to fetch release values from, by host.
+    pub forges: BTreeMap<String, ForgeKind>,
+}
+
+impl Config {
+    const CONFIG: &'static str = "KOMAC_CONFIG";
+
+    const FILE_NAME: &'static str = "config.yaml";
+
+    /// Returns the path of the configuration file, whether or not it exists.
+    pub fn path() -> Option<Utf8PathBuf> {
+        env::var(Self::CONFIG)
+            .ok()
+            .filter(|path| !path.is_empty())
+            .map(Utf8PathBuf::from)
+            .or_else(|| {
+                PlatformDir::Config
+                    .komac()
+                    .map(|dir| dir.join(Self::FILE_NAME))
+            })
+    }
+
+    /// Reads the configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration file exists but cannot be read or is invalid.
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(config) => serde_yaml::from_str(&config)
+                .wrap_err_with(|| format!("Failed to parse the configuration file at {path}")),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error)
+                .wrap_err_with(|| format!("Failed to read the configuration file at {path}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use crate::forge::ForgeKind;
+
+    #[test]
+    fn parse() {
+        let config = serde_yaml::from_str::<Config>(
+            r#"
+http:
+  user-agent: komac
+  connect-timeout: 10
+  headers:
+    Accept-Language: en-US
+  hosts:
+    downloads.example.com:
+      read-timeout: 60
+      headers:
+        Cookie: session=abc
+upstream:
+  repository: contoso/winget-pkgs
+  branch: main
+forges:
+  gitlab.example.com: gitlab
+  git.example.com: forgejo
+"#,
+        )
+        .unwrap();
+
+        let host = config.http.for_host("downloads.example.com");
+        assert_eq!(host.user_agent.as_deref(), Some("komac"));
+        assert_eq!(host.connect_timeout, Some(10));
+        assert_eq!(host.read_timeout, Some(60));
+        assert_eq!(host.headers.len(), 2);
+
+        let upstream = config.upstream.repository.unwrap();
+        assert_eq!(upstream.to_string(), "contoso/winget-pkgs");
+        assert_eq!(config.upstream.branch.as_deref(), Some("main"));
+
+        assert_eq!(config.forges["gitlab.example.com"], ForgeKind::GitLab);
+        assert_eq!(config.forges["git.example.com"], ForgeKind::Gitea);
+    }
+
+    #[test]
+    fn empty() {
+        let config = serde_yaml::from_str::<Config>("{}").unwrap();
+        assert!(config.http.for_host("example.com").user_agent.is_none());
+    }
+}
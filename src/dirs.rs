@@ -0,0 +1,44 @@
+use std::env;
+
+use camino::Utf8PathBuf;
+
+/// A per-user directory of the platform that Komac keeps its files in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PlatformDir {
+    Config,
+    Cache,
+}
+
+impl PlatformDir {
+    /// Returns `komac` inside the platform's per-user directory of this kind.
+    ///
+    /// This follows `%APPDATA%` or `%LOCALAPPDATA%` on Windows, `~/Library` on macOS, and the XDG
+    /// base directories elsewhere. Returns [`None`] if the environment variables they are based on
+    /// are not set.
+    pub fn komac(self) -> Option<Utf8PathBuf> {
+        let var = |key| env::var(key).ok().filter(|value| !value.is_empty());
+
+        let (windows, macos, xdg, xdg_default) = match self {
+            Self::Config => (
+                "APPDATA",
+                "Application Support",
+                "XDG_CONFIG_HOME",
+                ".config",
+            ),
+            Self::Cache => ("LOCALAPPDATA", "Caches", "XDG_CACHE_HOME", ".cache"),
+        };
+
+        let dir = if cfg!(windows) {
+            Utf8PathBuf::from(var(windows)?)
+        } else if cfg!(target_os = "macos") {
+            Utf8PathBuf::from(var("HOME")?).join("Library").join(macos)
+        } else {
+            var(xdg).map_or_else(
+                || Some(Utf8PathBuf::from(var("HOME")?).join(xdg_default)),
+                |dir| Some(Utf8PathBuf::from(dir)),
+            )?
+        };
+
+        Some(dir.join(env!("CARGO_PKG_NAME")))
+    }
+}
@@ -0,0 +1,331 @@
+use std::{
+    env,
+    fs::{self, File},
+    io,
+    io::Write,
+};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+use tracing::debug;
+use winget_types::Sha256String;
+
+use crate::dirs::PlatformDir;
+
+/// An opt-in, on-disk cache of downloaded installers.
+///
+/// Installers are stored once under `blobs`, named by the SHA-256 hash of their contents. Every
+/// URL that has been downloaded has an entry under `entries` recording the hash of what it served,
+/// along with its `ETag` and `Last-Modified` headers. These let a later download of the same URL be
+/// validated with a conditional request rather than downloading the installer again.
+#[derive(Clone, Debug)]
+pub struct DownloadCache {
+    root: Utf8PathBuf,
+}
+
+/// A URL that has been downloaded into the cache.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub url: String,
+    pub sha_256: Sha256String,
+    pub file_name: String,
+    pub size: u64,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub last_used: DateTime<Utc>,
+}
+
+/// What was removed when pruning the cache.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Pruned {
+    pub entries: usize,
+    pub blobs: usize,
+    pub bytes: u64,
+}
+
+impl DownloadCache {
+    const CACHE_DIR: &'static str = "KOMAC_CACHE_DIR";
+
+    const BLOBS: &'static str = "blobs";
+
+    const ENTRIES: &'static str = "entries";
+
+    const JSON: &'static str = "json";
+
+    pub fn new<P: Into<Utf8PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Opens the cache in the directory set by `KOMAC_CACHE_DIR`, falling back to the platform's
+    /// cache directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `KOMAC_CACHE_DIR` is not set and the platform's cache directory cannot
+    /// be determined from the environment.
+    pub fn from_env() -> io::Result<Self> {
+        env::var(Self::CACHE_DIR)
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .map(Utf8PathBuf::from)
+            .or_else(|| PlatformDir::Cache.komac())
+            .map(Self::new)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "Could not determine a cache directory. Set {} to choose one",
+                        Self::CACHE_DIR
+                    ),
+                )
+            })
+    }
+
+    #[inline]
+    pub fn root(&self) -> &Utf8Path {
+        &self.root
+    }
+
+    fn blob_path(&self, sha_256: &Sha256String) -> Utf8PathBuf {
+        self.root.join(Self::BLOBS).join(sha_256.as_str())
+    }
+
+    fn entry_path(&self, url: &str) -> Utf8PathBuf {
+        let key = Sha256String::from_digest(&Sha256::digest(url));
+        self.root
+            .join(Self::ENTRIES)
+            .join(key.as_str())
+            .with_extension(Self::JSON)
+    }
+
+    /// Returns the entry for a URL if its installer is still in the cache.
+    pub fn entry(&self, url: &str) -> Option<CacheEntry> {
+        let entry = fs::read(self.entry_path(url))
+            .ok()
+            .and_then(|entry| serde_json::from_slice::<CacheEntry>(&entry).ok())
+            .filter(|entry| entry.url == url)?;
+
+        self.blob_path(&entry.sha_256).is_file().then_some(entry)
+    }
+
+    /// Opens the cached installer for an entry and marks the entry as used.
+    pub fn open(&self, mut entry: CacheEntry) -> io::Result<(File, CacheEntry)> {
+        let file = File::open(self.blob_path(&entry.sha_256))?;
+        entry.last_used = Utc::now();
+        self.write_entry(&entry)?;
+        Ok((file, entry))
+    }
+
+    /// Creates a temporary file in the cache to download an installer into, so that it can be
+    /// moved into place without copying once its hash is known.
+    pub fn temp_file(&self) -> io::Result<NamedTempFile> {
+        let blobs = self.root.join(Self::BLOBS);
+        fs::create_dir_all(&blobs)?;
+        NamedTempFile::new_in(blobs)
+    }
+
+    /// Stores a downloaded installer and its entry, returning the stored installer.
+    ///
+    /// If an installer with the same hash is already cached, it is reused and the download is
+    /// discarded.
+    pub fn insert(&self, temp_file: NamedTempFile, entry: &CacheEntry) -> io::Result<File> {
+        let blob_path = self.blob_path(&entry.sha_256);
+
+        if blob_path.is_file() {
+            debug!("{} is already cached as {blob_path}", entry.url);
+        } else {
+            temp_file.persist(&blob_path)?;
+        }
+
+        self.write_entry(entry)?;
+
+        File::open(blob_path)
+    }
+
+    fn write_entry(&self, entry: &CacheEntry) -> io::Result<()> {
+        let entry_path = self.entry_path(&entry.url);
+        let entries = entry_path.parent().unwrap_or(&self.root);
+        fs::create_dir_all(entries)?;
+
+        // Write to a temporary file first so that a concurrent reader never sees a partial entry
+        let mut temp_file = NamedTempFile::new_in(entries)?;
+        temp_file.write_all(&serde_json::to_vec_pretty(entry)?)?;
+        temp_file.persist(entry_path)?;
+        Ok(())
+    }
+
+    /// Returns every entry in the cache, most recently used first.
+    pub fn entries(&self) -> io::Result<Vec<CacheEntry>> {
+        let entries_dir = self.root.join(Self::ENTRIES);
+        if !entries_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = fs::read_dir(entries_dir)?
+            .filter_map(Result::ok)
+            .filter_map(|dir_entry| fs::read(dir_entry.path()).ok())
+            .filter_map(|entry| serde_json::from_slice::<CacheEntry>(&entry).ok())
+            .collect::<Vec<_>>();
+
+        entries.sort_unstable_by_key(|entry| std::cmp::Reverse(entry.last_used));
+
+        Ok(entries)
+    }
+
+    /// Removes entries that have not been used within `max_age`, along with any installers that
+    /// are no longer referenced by an entry.
+    pub fn prune(&self, max_age: TimeDelta) -> io::Result<Pruned> {
+        let mut pruned = Pruned::default();
+
+        let cutoff = Utc::now() - max_age;
+        let mut kept = Vec::new();
+        for entry in self.entries()? {
+            if entry.last_used < cutoff {
+                fs::remove_file(self.entry_path(&entry.url))?;
+                pruned.entries += 1;
+            } else {
+                kept.push(entry.sha_256);
+            }
+        }
+
+        let blobs_dir = self.root.join(Self::BLOBS);
+        if !blobs_dir.is_dir() {
+            return Ok(pruned);
+        }
+
+        for blob in fs::read_dir(blobs_dir)? {
+            let blob = blob?;
+            let file_name = blob.file_name();
+            let Some(name) = file_name.to_str() else {
+                continue;
+            };
+
+            // Leave downloads that are still in progress alone
+            if name.starts_with('.') {
+                continue;
+            }
+
+            if !kept.iter().any(|sha_256| sha_256.as_str() == name) {
+                pruned.bytes += blob.metadata()?.len();
+                fs::remove_file(blob.path())?;
+                pruned.blobs += 1;
+            }
+        }
+
+        Ok(pruned)
+    }
+
+    /// Removes everything in the cache, returning the number of bytes freed.
+    pub fn clear(&self) -> io::Result<u64> {
+        if !self.root.is_dir() {
+            return Ok(0);
+        }
+
+        let size = walkdir::WalkDir::new(&self.root)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(fs::Metadata::is_file)
+            .map(|metadata| metadata.len())
+            .sum();
+
+        fs::remove_dir_all(&self.root)?;
+
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use chrono::{TimeDelta, Utc};
+    use sha2::{Digest, Sha256};
+    use winget_types::Sha256String;
+
+    use super::{CacheEntry, DownloadCache};
+
+    fn cache() -> (tempfile::TempDir, DownloadCache) {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DownloadCache::new(dir.path().to_str().unwrap());
+        (dir, cache)
+    }
+
+    fn insert(cache: &DownloadCache, url: &str, contents: &[u8]) -> CacheEntry {
+        let mut temp_file = cache.temp_file().unwrap();
+        temp_file.write_all(contents).unwrap();
+
+        let entry = CacheEntry {
+            url: url.to_owned(),
+            sha_256: Sha256String::from_digest(&Sha256::digest(contents)),
+            file_name: "setup.exe".to_owned(),
+            size: contents.len() as u64,
+            etag: Some(r#""abc""#.to_owned()),
+            last_modified: None,
+            last_used: Utc::now(),
+        };
+        cache.insert(temp_file, &entry).unwrap();
+        entry
+    }
+
+    #[test]
+    fn insert_and_lookup() {
+        let (_dir, cache) = cache();
+        let url = "https://example.com/setup.exe";
+        insert(&cache, url, b"installer");
+
+        let entry = cache.entry(url).unwrap();
+        assert_eq!(entry.etag.as_deref(), Some(r#""abc""#));
+
+        let (mut file, _) = cache.open(entry).unwrap();
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "installer");
+
+        assert!(cache.entry("https://example.com/other.exe").is_none());
+    }
+
+    #[test]
+    fn identical_contents_are_stored_once() {
+        let (dir, cache) = cache();
+        insert(&cache, "https://example.com/a.exe", b"installer");
+        insert(&cache, "https://mirror.example.com/a.exe", b"installer");
+
+        assert_eq!(cache.entries().unwrap().len(), 2);
+        assert_eq!(
+            std::fs::read_dir(dir.path().join(DownloadCache::BLOBS))
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn prune() {
+        let (_dir, cache) = cache();
+        let mut old = insert(&cache, "https://example.com/old.exe", b"old");
+        old.last_used = Utc::now() - TimeDelta::days(60);
+        cache.write_entry(&old).unwrap();
+        insert(&cache, "https://example.com/new.exe", b"new");
+
+        let pruned = cache.prune(TimeDelta::days(30)).unwrap();
+        assert_eq!(pruned.entries, 1);
+        assert_eq!(pruned.blobs, 1);
+        assert_eq!(pruned.bytes, 3);
+        assert!(cache.entry("https://example.com/old.exe").is_none());
+        assert!(cache.entry("https://example.com/new.exe").is_some());
+    }
+
+    #[test]
+    fn clear() {
+        let (dir, cache) = cache();
+        insert(&cache, "https://example.com/setup.exe", b"installer");
+
+        // The installer itself and its entry
+        assert!(cache.clear().unwrap() > 9);
+        assert!(!dir.path().exists());
+    }
+}
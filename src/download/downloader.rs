@@ -1,31 +1,44 @@
-use std::{fmt, num::NonZeroUsize};
+use std::{
+    fmt,
+    io::{self, SeekFrom},
+    num::NonZeroUsize,
+};
 
-use chrono::DateTime;
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, Utc};
 use color_eyre::{Result, eyre::bail};
 use futures_util::{StreamExt, TryStreamExt, stream};
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
-use itertools::{Itertools, Position};
+use indicatif::{HumanBytes, MultiProgress, ProgressBar, ProgressStyle};
+use itertools::Itertools;
 use reqwest::{
-    Client,
+    Client, StatusCode,
     header::{
-        CONTENT_DISPOSITION, CONTENT_TYPE, GetAll, HeaderMap, HeaderValue, LAST_MODIFIED,
-        USER_AGENT,
+        ACCEPT_RANGES, CONTENT_DISPOSITION, CONTENT_TYPE, ETAG, HeaderMap, HeaderValue,
+        IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_RANGE, LAST_MODIFIED, RANGE, USER_AGENT,
     },
 };
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, digest::Output};
 use thiserror::Error;
 use tokio::{
-    io::{AsyncWriteExt, BufWriter},
+    io::{AsyncSeekExt, AsyncWriteExt, BufWriter},
     sync::mpsc,
+    time::sleep,
     try_join,
 };
+use tracing::{debug, warn};
 use winget_types::Sha256String;
 
-use super::{Download, DownloadedFile, Downloads};
+use super::{
+    CacheEntry, Download, DownloadCache, DownloadedFile, Downloads, HttpClients, HttpConfig,
+    HttpConfigError,
+    retry::{self, MAX_RETRIES},
+    sniff::{Payload, SNIFF_LEN, TITLE_SEARCH_LEN, html_title},
+};
 
 pub struct Downloader {
-    client: Client,
+    clients: HttpClients,
     concurrent_downloads: NonZeroUsize,
+    cache: Option<DownloadCache>,
 }
 
 impl Downloader {
@@ -36,25 +49,23 @@ impl Downloader {
 
     const PROGRESS_CHARS: &'static str = "───";
 
-    const APPLICATION: &'static str = "application";
-
-    const OCTET_STREAM: &'static str = "octet-stream";
-
     /// Creates a new Downloader with a maximum number of concurrent downloads of the number of
     /// logical cores the system has.
     ///
     /// # Errors
     ///
-    /// Propagates the error from [`ClientBuilder::build`] which fails if a TLS backend cannot be
-    /// initialized, or the resolver cannot load the system configuration.
+    /// Returns an error if a configured header or proxy is invalid, or propagates the error from
+    /// [`ClientBuilder::build`] which fails if a TLS backend cannot be initialized, or the
+    /// resolver cannot load the system configuration.
     ///
     /// [`ClientBuilder::build`]: reqwest::ClientBuilder::build
     #[expect(unused)]
-    pub fn new() -> reqwest::Result<Self> {
+    pub fn new(http_config: &HttpConfig) -> Result<Self, HttpConfigError> {
         Self::new_with_concurrent(
             num_cpus::get()
                 .try_into()
                 .unwrap_or_else(|_| unreachable!("num_cpus::get should always returns at least 1")),
+            http_config,
         )
     }
 
@@ -62,23 +73,33 @@ pub fn new() -> reqwest::Result<Self> {
     ///
     /// # Errors
     ///
-    /// Propagates the error from [`ClientBuilder::build`] which fails if a TLS backend cannot be
-    /// initialized, or the resolver cannot load the system configuration.
+    /// Returns an error if a configured header or proxy is invalid, or propagates the error from
+    /// [`ClientBuilder::build`] which fails if a TLS backend cannot be initialized, or the
+    /// resolver cannot load the system configuration.
     ///
     /// [`ClientBuilder::build`]: reqwest::ClientBuilder::build
-    pub fn new_with_concurrent(concurrent_downloads: NonZeroUsize) -> reqwest::Result<Self> {
+    pub fn new_with_concurrent(
+        concurrent_downloads: NonZeroUsize,
+        http_config: &HttpConfig,
+    ) -> Result<Self, HttpConfigError> {
         Ok(Self {
-            client: Client::builder()
-                .default_headers(Self::headers())
-                .referer(false)
-                .build()?,
+            clients: HttpClients::new(http_config, &Self::headers())?,
             concurrent_downloads,
+            cache: None,
         })
     }
 
+    /// Stores downloaded files in the given cache, reusing previously downloaded files that the
+    /// server confirms have not changed.
+    pub fn with_cache(mut self, cache: Option<DownloadCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
     /// Downloads the files at the given URLs to temporary files.
     ///
-    /// A file is deleted when its [`DownloadedFile`] is dropped.
+    /// A file is deleted when its [`DownloadedFile`] is dropped, unless a cache is used, in which
+    /// case it is kept in the cache.
     pub async fn download<I, D>(&self, downloads: I) -> Result<Downloads>
     where
         I: IntoIterator<Item = D>,
@@ -87,7 +108,10 @@ pub async fn download<I, D>(&self, downloads: I) -> Result<Downloads>
         let multi_progress = MultiProgress::new();
 
         let downloaded_files = stream::iter(downloads.into_iter().map(D::into).unique())
-            .map(|download| self.fetch(&self.client, download, &multi_progress))
+            .map(|download| {
+                let client = self.clients.get(download.url());
+                self.fetch(client, download, &multi_progress)
+            })
             .buffer_unordered(self.concurrent_downloads.get())
             .try_collect::<Downloads>()
             .await?;
@@ -97,7 +121,8 @@ pub async fn download<I, D>(&self, downloads: I) -> Result<Downloads>
         Ok(downloaded_files)
     }
 
-    /// Returns a [`HeaderMap`] of the default headers komac uses.
+    /// Returns a [`HeaderMap`] of the default headers komac uses, unless they are replaced by the
+    /// HTTP configuration.
     ///
     /// * `user-agent`: `Microsoft-Delivery-Optimization/10.1`
     /// * `Sec-GPC`: `1`
@@ -112,24 +137,6 @@ fn headers() -> HeaderMap {
         headers
     }
 
-    fn check_content_types(
-        download: &Download,
-        content_types: GetAll<HeaderValue>,
-    ) -> Result<(), ContentTypeError> {
-        if content_types.iter().all(|content_type| {
-            !content_type
-                .as_bytes()
-                .ends_with(Self::OCTET_STREAM.as_bytes())
-                && !content_type
-                    .as_bytes()
-                    .starts_with(Self::APPLICATION.as_bytes())
-        }) {
-            return Err(ContentTypeError::new(download.clone(), content_types));
-        }
-
-        Ok(())
-    }
-
     pub async fn fetch(
         &self,
         client: &Client,
@@ -140,7 +147,42 @@ pub async fn fetch(
 
         download.upgrade_to_https(client).await;
 
-        let res = client.get((***download.url()).clone()).send().await?;
+        let cached = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.entry(download.as_str()))
+            .filter(|entry| entry.etag.is_some() || entry.last_modified.is_some());
+
+        let res = self
+            .clients
+            .send(download.url(), |client, url| {
+                let mut request = client.get(url);
+                if let Some(entry) = &cached {
+                    if let Some(etag) = &entry.etag {
+                        request = request.header(IF_NONE_MATCH, etag);
+                    }
+                    if let Some(last_modified) = &entry.last_modified {
+                        request = request.header(IF_MODIFIED_SINCE, last_modified);
+                    }
+                }
+                request
+            })
+            .await?;
+
+        if res.status() == StatusCode::NOT_MODIFIED
+            && let Some(cache) = &self.cache
+            && let Some(entry) = cached
+        {
+            debug!("{download} has not changed since it was cached");
+            let (file, entry) = cache.open(entry)?;
+            return Ok(DownloadedFile {
+                url: download.into_url(),
+                file,
+                sha_256: entry.sha_256,
+                file_name: entry.file_name,
+                last_modified: entry.last_modified.as_deref().and_then(parse_http_date),
+            });
+        }
 
         if let Err(err) = res.error_for_status_ref() {
             bail!(
@@ -150,21 +192,73 @@ pub async fn fetch(
             )
         }
 
-        // Check that we're downloading an application
-        Self::check_content_types(&download, res.headers().get_all(CONTENT_TYPE))?;
+        if res.status().is_redirection() {
+            bail!("{download} redirected too many times");
+        }
 
         let file_name = download
             .file_name(res.url(), res.headers().get(CONTENT_DISPOSITION))
             .into_owned();
 
-        let last_modified = res
+        let header = |name| {
+            res.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let etag = header(ETAG);
+        let last_modified_header = header(LAST_MODIFIED);
+        let last_modified = last_modified_header.as_deref().and_then(parse_http_date);
+
+        let final_url = res.url().clone();
+        let supports_ranges = res
             .headers()
-            .get(LAST_MODIFIED)
-            .and_then(|last_modified| last_modified.to_str().ok())
-            .and_then(|last_modified| DateTime::parse_from_rfc2822(last_modified).ok())
-            .map(|date_time| date_time.date_naive());
+            .get(ACCEPT_RANGES)
+            .is_some_and(|accept_ranges| accept_ranges.as_bytes().eq_ignore_ascii_case(b"bytes"));
+
+        // Only a strong validator guarantees that a resumed range is from the same file
+        let if_range = etag
+            .as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(last_modified_header.as_deref())
+            .map(str::to_owned);
+
+        let content_type = header(CONTENT_TYPE);
+        let content_length = res.content_length();
+        let mut stream = res.bytes_stream();
 
-        let progress_bar = match res.content_length() {
+        // Inspect the start of the body rather than trusting its content type, which is often
+        // wrong or missing, so that only a web page served in place of the installer is rejected
+        let mut head = Vec::new();
+        let mut head_chunks = Vec::new();
+        while head.len() < SNIFF_LEN
+            && let Some(chunk) = stream.next().await
+        {
+            let chunk = chunk?;
+            head.extend_from_slice(&chunk);
+            head_chunks.push(chunk);
+        }
+
+        match Payload::sniff(&head) {
+            Payload::Html => {
+                while head.len() < TITLE_SEARCH_LEN
+                    && let Some(Ok(chunk)) = stream.next().await
+                {
+                    head.extend_from_slice(&chunk);
+                }
+                return Err(HtmlError {
+                    download: Box::new(download),
+                    title: html_title(&head),
+                }
+                .into());
+            }
+            Payload::Unknown => debug!(
+                "{download} does not start with a known installer signature. Content-Type: {content_type:?}"
+            ),
+            Payload::Installer => {}
+        }
+
+        let progress_bar = match content_length {
             Some(len) => ProgressBar::new(len).with_style(
                 ProgressStyle::with_template(Self::PROGRESS_TEMPLATE)?
                     .progress_chars(Self::PROGRESS_CHARS),
@@ -177,39 +271,101 @@ pub async fn fetch(
         let progress =
             multi_progress.add(progress_bar.with_message(format!("Downloading {download}")));
 
-        // Create a temporary file
-        let temp_file = tempfile::tempfile()?;
+        // Create a temporary file, inside the cache if there is one so that it can be kept
+        let cache_file = self
+            .cache
+            .as_ref()
+            .map(DownloadCache::temp_file)
+            .transpose()?;
+        let temp_file = match &cache_file {
+            Some(cache_file) => cache_file.as_file().try_clone()?,
+            None => tempfile::tempfile()?,
+        };
         let file = tokio::fs::File::from_std(temp_file.try_clone()?);
-        let mut buf_writer = BufWriter::new(file);
 
         // Create a thread for writing to the file
-        let (write_sender, mut write_receiver) = mpsc::unbounded_channel::<bytes::Bytes>();
-        let writer = tokio::spawn(async move {
-            while let Some(chunk) = write_receiver.recv().await {
-                buf_writer.write_all(&chunk).await?;
-            }
-
-            buf_writer.flush().await?;
-            buf_writer.shutdown().await
-        });
+        let (write_sender, write_receiver) = mpsc::unbounded_channel::<Chunk>();
+        let writer = tokio::spawn(write_chunks(file, write_receiver));
 
         // Create a thread for hashing the downloaded bytes
-        let (hash_sender, hash_receiver) = crossbeam_channel::unbounded::<bytes::Bytes>();
-        let hasher = tokio::task::spawn_blocking(move || {
-            let mut hasher = Sha256::new();
-            while let Ok(chunk) = hash_receiver.recv() {
-                hasher.update(&chunk);
-            }
-            hasher.finalize()
-        });
-
-        let mut stream = res.bytes_stream();
+        let (hash_sender, hash_receiver) = crossbeam_channel::unbounded::<Chunk>();
+        let hasher = tokio::task::spawn_blocking(move || hash_chunks(&hash_receiver));
 
-        // Download the chunks asynchronously
-        while let Some(chunk) = stream.next().await.transpose()? {
+        let mut size = 0;
+        for chunk in head_chunks {
             progress.inc(chunk.len() as u64);
-            hash_sender.send(chunk.clone())?;
-            write_sender.send(chunk)?;
+            size += chunk.len() as u64;
+            hash_sender.send(Chunk::Bytes(chunk.clone()))?;
+            write_sender.send(Chunk::Bytes(chunk))?;
+        }
+
+        let mut retry = 0;
+
+        // Download the chunks asynchronously, resuming from where the download stopped if the
+        // connection is interrupted. The chunks of each resumed range follow on from the previous
+        // ones, so they can be written and hashed as if they were a single response. If the server
+        // does not support ranges, the download is restarted from the beginning instead.
+        loop {
+            let error = match stream.next().await {
+                Some(Ok(chunk)) => {
+                    progress.inc(chunk.len() as u64);
+                    size += chunk.len() as u64;
+                    hash_sender.send(Chunk::Bytes(chunk.clone()))?;
+                    write_sender.send(Chunk::Bytes(chunk))?;
+                    continue;
+                }
+                Some(Err(error)) => error,
+                None => break,
+            };
+
+            let recovery = Recovery::new(supports_ranges, retry::is_transient_error(&error), retry);
+            if recovery == Recovery::Fail {
+                return Err(error.into());
+            }
+
+            retry += 1;
+            let delay = retry::backoff(retry);
+            warn!(
+                "The download of {download} was interrupted after {}: {error}. {} in {delay:?} ({retry}/{MAX_RETRIES})",
+                HumanBytes(size),
+                recovery.as_str()
+            );
+            sleep(delay).await;
+
+            let res = self
+                .clients
+                .send(&final_url, |client, url| {
+                    let mut request = client.get(url);
+                    if recovery == Recovery::Resume {
+                        request = request.header(RANGE, format!("bytes={size}-"));
+                        if let Some(if_range) = &if_range {
+                            request = request.header(IF_RANGE, if_range);
+                        }
+                    }
+                    request
+                })
+                .await?;
+            match res.status() {
+                StatusCode::PARTIAL_CONTENT if recovery == Recovery::Resume => {}
+                // The whole file was sent again, either because the download is being restarted or
+                // because the file changed since the download started
+                StatusCode::OK => {
+                    progress.set_position(0);
+                    size = 0;
+                    hash_sender.send(Chunk::Restart)?;
+                    write_sender.send(Chunk::Restart)?;
+                }
+                status => bail!(
+                    "The download of {download} could not be {} as {} returned {status}",
+                    if recovery == Recovery::Resume {
+                        "resumed"
+                    } else {
+                        "restarted"
+                    },
+                    res.url()
+                ),
+            }
+            stream = res.bytes_stream();
         }
 
         drop(write_sender);
@@ -222,59 +378,207 @@ pub async fn fetch(
 
         progress.finish();
 
+        let sha_256 = Sha256String::from_digest(&sha_256);
+
+        let file = match (&self.cache, cache_file) {
+            (Some(cache), Some(cache_file)) => cache.insert(
+                cache_file,
+                &CacheEntry {
+                    url: download.as_str().to_owned(),
+                    sha_256: sha_256.clone(),
+                    file_name: file_name.clone(),
+                    size,
+                    etag,
+                    last_modified: last_modified_header,
+                    last_used: Utc::now(),
+                },
+            )?,
+            _ => temp_file,
+        };
+
         Ok(DownloadedFile {
             url: download.into_url(),
-            file: temp_file,
-            sha_256: Sha256String::from_digest(&sha_256),
+            file,
+            sha_256,
             file_name,
             last_modified,
         })
     }
 }
 
-#[derive(Debug, Error)]
-pub struct ContentTypeError {
-    download: Download,
-    content_types: Vec<HeaderValue>,
+/// A message to the tasks that write and hash a download.
+#[derive(Clone)]
+enum Chunk {
+    Bytes(Bytes),
+    /// Discards everything received so far, as the download is starting again from the beginning.
+    Restart,
 }
 
-impl ContentTypeError {
-    pub fn new<D, I, C>(download: D, content_types: I) -> Self
-    where
-        D: Into<Download>,
-        I: IntoIterator<Item = C>,
-        C: Into<HeaderValue>,
-    {
-        Self {
-            download: download.into(),
-            content_types: content_types.into_iter().map(C::into).collect(),
+/// How to continue a download after its connection is interrupted.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Recovery {
+    /// Request the rest of the file from where the download stopped.
+    Resume,
+    /// Download the whole file again, as the server does not support ranges.
+    Restart,
+    /// Give up on the download.
+    Fail,
+}
+
+impl Recovery {
+    const fn new(supports_ranges: bool, is_transient: bool, retry: u32) -> Self {
+        if !is_transient || retry == MAX_RETRIES {
+            Self::Fail
+        } else if supports_ranges {
+            Self::Resume
+        } else {
+            Self::Restart
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Resume => "Resuming",
+            Self::Restart => "Restarting",
+            Self::Fail => "Failing",
         }
     }
 }
 
-impl fmt::Display for ContentTypeError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "The content type for {} was ", self.download)?;
-        for (position, content_type) in self
-            .content_types
-            .iter()
-            .flat_map(HeaderValue::to_str)
-            .with_position()
-        {
-            match position {
-                Position { is_first: true, .. } => write!(f, "{content_type:?}")?,
-                Position {
-                    is_first: false,
-                    is_last: false,
-                } => write!(f, ", {content_type:?}")?,
-                Position { is_last: true, .. } => write!(f, " and {content_type:?}")?,
+/// Writes the chunks of a download to a file, truncating it whenever the download restarts.
+async fn write_chunks(
+    file: tokio::fs::File,
+    mut receiver: mpsc::UnboundedReceiver<Chunk>,
+) -> io::Result<()> {
+    let mut buf_writer = BufWriter::new(file);
+    while let Some(chunk) = receiver.recv().await {
+        match chunk {
+            Chunk::Bytes(bytes) => buf_writer.write_all(&bytes).await?,
+            Chunk::Restart => {
+                buf_writer.flush().await?;
+                let file = buf_writer.get_mut();
+                file.set_len(0).await?;
+                file.seek(SeekFrom::Start(0)).await?;
             }
         }
+    }
+
+    buf_writer.flush().await?;
+    buf_writer.shutdown().await
+}
+
+/// Hashes the chunks of a download, starting over whenever the download restarts.
+fn hash_chunks(receiver: &crossbeam_channel::Receiver<Chunk>) -> Output<Sha256> {
+    let mut hasher = Sha256::new();
+    while let Ok(chunk) = receiver.recv() {
+        match chunk {
+            Chunk::Bytes(bytes) => hasher.update(&bytes),
+            Chunk::Restart => hasher.reset(),
+        }
+    }
+    hasher.finalize()
+}
+
+/// Parses the date from an HTTP date header, such as `Last-Modified`.
+fn parse_http_date(date: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc2822(date)
+        .ok()
+        .map(|date_time| date_time.date_naive())
+}
+
+/// The error returned when a web page is downloaded instead of an installer, such as a login wall
+/// or a download landing page.
+#[derive(Debug, Error)]
+pub struct HtmlError {
+    download: Box<Download>,
+    title: Option<String>,
+}
+
+impl fmt::Display for HtmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} returned a web page", self.download)?;
+        if let Some(title) = &self.title {
+            write!(f, r#" titled "{title}""#)?;
+        }
         write!(
             f,
-            " but an {application} or {octet_stream} content type was expected",
-            application = Downloader::APPLICATION,
-            octet_stream = Downloader::OCTET_STREAM
+            " instead of an installer. The URL may require signing in or lead to a download page rather than the installer itself"
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Seek, SeekFrom};
+
+    use bytes::Bytes;
+    use rstest::rstest;
+    use sha2::{Digest, Sha256};
+    use tokio::sync::mpsc;
+
+    use super::{Chunk, MAX_RETRIES, Recovery, hash_chunks, write_chunks};
+
+    #[rstest]
+    #[case::resume(true, true, 0, Recovery::Resume)]
+    #[case::restart(false, true, 0, Recovery::Restart)]
+    #[case::not_transient(true, false, 0, Recovery::Fail)]
+    #[case::out_of_retries(true, true, MAX_RETRIES, Recovery::Fail)]
+    #[case::restart_out_of_retries(false, true, MAX_RETRIES, Recovery::Fail)]
+    fn recovery(
+        #[case] supports_ranges: bool,
+        #[case] is_transient: bool,
+        #[case] retry: u32,
+        #[case] expected: Recovery,
+    ) {
+        assert_eq!(
+            Recovery::new(supports_ranges, is_transient, retry),
+            expected
+        );
+    }
+
+    #[test]
+    fn hash_restarts() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        sender
+            .send(Chunk::Bytes(Bytes::from_static(b"partial")))
+            .unwrap();
+        sender.send(Chunk::Restart).unwrap();
+        sender
+            .send(Chunk::Bytes(Bytes::from_static(b"com")))
+            .unwrap();
+        sender
+            .send(Chunk::Bytes(Bytes::from_static(b"plete")))
+            .unwrap();
+        drop(sender);
+
+        assert_eq!(hash_chunks(&receiver), Sha256::digest(b"complete"));
+    }
+
+    #[tokio::test]
+    async fn write_restarts() {
+        let mut file = tempfile::tempfile().unwrap();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        sender
+            .send(Chunk::Bytes(Bytes::from_static(
+                b"a longer partial download",
+            )))
+            .unwrap();
+        sender.send(Chunk::Restart).unwrap();
+        sender
+            .send(Chunk::Bytes(Bytes::from_static(b"complete")))
+            .unwrap();
+        drop(sender);
+
+        write_chunks(
+            tokio::fs::File::from_std(file.try_clone().unwrap()),
+            receiver,
+        )
+        .await
+        .unwrap();
+
+        let mut contents = String::new();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "complete");
+    }
+}
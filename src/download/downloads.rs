@@ -1,16 +1,24 @@
 use std::{
-    collections::HashMap,
-    io::{Read, Seek},
+    collections::{BTreeSet, HashMap},
     mem,
 };
 
 use color_eyre::Result;
 use futures_util::{StreamExt, TryStreamExt, stream};
+use indicatif::{MultiProgress, ProgressBar};
+use tokio::task;
 use tracing::debug;
 use winget_types::{installer::Architecture, url::DecodedUrl};
 
 use super::DownloadedFile;
-use crate::analysis::Analyzer;
+use crate::{
+    analysis::{
+        Analyzer,
+        explain::{ARCHITECTURE, explain},
+    },
+    commands::utils::SPINNER_TICK_RATE,
+    read::MappedReader,
+};
 
 #[derive(Default)]
 pub struct Downloads(Vec<DownloadedFile>);
@@ -25,36 +33,87 @@ pub fn new<I>(downloads: I) -> Self
         Self(downloads.into_iter().collect())
     }
 
-    pub async fn analyze(&mut self) -> Result<HashMap<DecodedUrl, Analyzer<'_, impl Read + Seek>>> {
-        stream::iter(self.0.iter_mut().map(
-            |DownloadedFile {
-                 file,
-                 url,
-                 sha_256,
-                 file_name,
-                 last_modified,
-                 ..
-             }| async move {
-                let mut file_analyzer = Analyzer::new(file, file_name)?;
-                let architecture = url
-                    .override_architecture()
-                    .or_else(|| Architecture::from_url(url.as_str()));
-                for installer in &mut file_analyzer.installers {
-                    if let Some(architecture) = architecture {
-                        installer.architecture = architecture;
-                    }
-                    debug!("{url}: {architecture:?}");
-                    installer.url = url.inner().clone();
-                    installer.sha_256 = sha_256.clone();
-                    installer.release_date = *last_modified;
-                }
-                file_analyzer.file_name = mem::take(file_name);
-                Ok((mem::take(url.inner_mut()), file_analyzer))
-            },
-        ))
+    /// Analyzes each file on the blocking thread pool, so that parsing large installers scales
+    /// with the number of cores without stalling the async runtime.
+    pub async fn analyze(self) -> Result<HashMap<DecodedUrl, Analyzer<MappedReader>>> {
+        let multi_progress = MultiProgress::new();
+
+        let analyzers = stream::iter(self.0.into_iter().map(|downloaded_file| {
+            let multi_progress = &multi_progress;
+            async move {
+                let progress = multi_progress.add(
+                    ProgressBar::new_spinner()
+                        .with_message(format!("Analyzing {}", downloaded_file.file_name)),
+                );
+                progress.enable_steady_tick(SPINNER_TICK_RATE);
+
+                let analyzer = task::spawn_blocking(|| Self::analyze_file(downloaded_file)).await?;
+
+                progress.finish_and_clear();
+                analyzer
+            }
+        }))
         .buffer_unordered(num_cpus::get())
         .try_collect::<HashMap<_, _>>()
-        .await
+        .await;
+
+        multi_progress.clear()?;
+
+        analyzers
+    }
+
+    fn analyze_file(
+        DownloadedFile {
+            file,
+            mut url,
+            sha_256,
+            file_name,
+            last_modified,
+        }: DownloadedFile,
+    ) -> Result<(DecodedUrl, Analyzer<MappedReader>)> {
+        let mut file_analyzer = Analyzer::new(MappedReader::new(file), &file_name)?;
+        // A file that already holds installers for several architectures, such as a bundle or a
+        // multi-architecture zip, should not have them collapsed into the single architecture
+        // guessed from its URL
+        let is_multi_architecture = file_analyzer
+            .installers
+            .iter()
+            .map(|installer| installer.architecture)
+            .collect::<BTreeSet<_>>()
+            .len()
+            > 1;
+        let architecture = if let Some(architecture) = url.override_architecture() {
+            explain(
+                ARCHITECTURE,
+                architecture,
+                "URL",
+                format_args!("the architecture was overridden for {url}"),
+            );
+            Some(architecture)
+        } else if is_multi_architecture {
+            None
+        } else {
+            Architecture::from_url(url.as_str()).inspect(|&architecture| {
+                explain(
+                    ARCHITECTURE,
+                    architecture,
+                    "URL",
+                    format_args!("the architecture is in the URL {url}"),
+                );
+            })
+        };
+        for installer in &mut file_analyzer.installers {
+            if let Some(architecture) = architecture {
+                installer.architecture = architecture;
+            }
+            url.overrides().apply(installer);
+            debug!("{url}: {architecture:?}");
+            installer.url = url.inner().clone();
+            installer.sha_256 = sha_256.clone();
+            installer.release_date = last_modified;
+        }
+        file_analyzer.file_name = file_name;
+        Ok((mem::take(url.inner_mut()), file_analyzer))
     }
 }
 
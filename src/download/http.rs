@@ -0,0 +1,337 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use reqwest::{
+    Client, Proxy, RequestBuilder, Response,
+    header::{HeaderMap, HeaderName, HeaderValue, LOCATION, USER_AGENT},
+    redirect::Policy,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::retry;
+
+/// The maximum number of redirects to follow, matching reqwest's default.
+const MAX_REDIRECTS: usize = 10;
+
+/// How HTTP clients are configured, either for every host or for a single one.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ClientConfig {
+    /// Replaces komac's default user agent.
+    pub user_agent: Option<String>,
+
+    /// Sends every request through this proxy instead of the system proxy.
+    pub proxy: Option<url::Url>,
+
+    /// The number of seconds to wait for a connection to be established.
+    pub connect_timeout: Option<u64>,
+
+    /// The number of seconds to wait for each read from a connection.
+    pub read_timeout: Option<u64>,
+
+    /// Extra headers sent with every request, such as an authorization header or a cookie.
+    pub headers: BTreeMap<String, String>,
+}
+
+impl ClientConfig {
+    /// Returns this configuration with any values set in `other` replacing its own.
+    #[must_use]
+    pub fn merge(mut self, other: &Self) -> Self {
+        if other.user_agent.is_some() {
+            self.user_agent.clone_from(&other.user_agent);
+        }
+        if other.proxy.is_some() {
+            self.proxy.clone_from(&other.proxy);
+        }
+        self.connect_timeout = other.connect_timeout.or(self.connect_timeout);
+        self.read_timeout = other.read_timeout.or(self.read_timeout);
+        self.headers.extend(
+            other
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        self
+    }
+
+    /// Builds a client that sends `headers` along with the configured headers.
+    ///
+    /// A client for a configured host only follows redirects to URLs that the same host
+    /// configuration applies to.
+    fn build(
+        &self,
+        mut headers: HeaderMap,
+        host: Option<HostScope>,
+    ) -> Result<Client, HttpConfigError> {
+        if let Some(user_agent) = &self.user_agent {
+            headers.insert(
+                USER_AGENT,
+                HeaderValue::from_str(user_agent)
+                    .map_err(|_| HttpConfigError::InvalidHeaderValue(USER_AGENT.to_string()))?,
+            );
+        }
+
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| HttpConfigError::InvalidHeaderName(name.clone()))?;
+            let mut value = HeaderValue::from_str(value)
+                .map_err(|_| HttpConfigError::InvalidHeaderValue(name.to_string()))?;
+
+            // Custom headers are often credentials, so keep them out of debug output
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+
+        let mut builder = Client::builder().default_headers(headers).referer(false);
+        if let Some(host) = host {
+            builder = builder.redirect(host.redirect_policy());
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy.as_str())?);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(Duration::from_secs(connect_timeout));
+        }
+        if let Some(read_timeout) = self.read_timeout {
+            builder = builder.read_timeout(Duration::from_secs(read_timeout));
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+/// The `http` section of the configuration file.
+///
+/// Top-level values apply to every host. Values under `hosts` apply to a host and its subdomains,
+/// taking precedence over the top-level values. Overrides from the command line take precedence
+/// over both.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct HttpConfig {
+    #[serde(flatten)]
+    defaults: ClientConfig,
+
+    hosts: BTreeMap<String, ClientConfig>,
+
+    #[serde(skip)]
+    overrides: ClientConfig,
+}
+
+impl HttpConfig {
+    /// Applies overrides that take precedence over the configuration of every host.
+    #[must_use]
+    pub fn with_overrides(mut self, overrides: ClientConfig) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Returns the configuration that applies to a host.
+    pub fn for_host(&self, host: &str) -> ClientConfig {
+        let config = self.defaults.clone();
+        let config = match match_host(&self.hosts, host) {
+            Some(host_config) => config.merge(host_config),
+            None => config,
+        };
+        config.merge(&self.overrides)
+    }
+}
+
+/// Finds the value for a host, preferring an exact match and then the longest matching parent
+/// domain.
+fn match_host<'map, T>(hosts: &'map BTreeMap<String, T>, host: &str) -> Option<&'map T> {
+    match_domain(hosts.keys(), host).and_then(|domain| hosts.get(domain))
+}
+
+/// Finds the domain that applies to a host, preferring an exact match and then the longest
+/// matching parent domain.
+fn match_domain<'domain, I>(domains: I, host: &str) -> Option<&'domain str>
+where
+    I: IntoIterator<Item = &'domain String>,
+{
+    domains
+        .into_iter()
+        .filter(|domain| {
+            *domain == host
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|subdomain| subdomain.ends_with('.'))
+        })
+        .max_by_key(|domain| domain.len())
+        .map(String::as_str)
+}
+
+/// A configured host along with every other configured host, used to keep a host's client from
+/// following redirects to hosts that its configuration does not apply to.
+struct HostScope {
+    domain: String,
+    domains: Arc<Vec<String>>,
+}
+
+impl HostScope {
+    /// Returns a redirect policy that stops at redirects to URLs that another configuration
+    /// applies to, so that the host's headers, which may be credentials, are not sent to them.
+    /// Reqwest only strips its own sensitive headers, such as `Authorization`, on such redirects.
+    fn redirect_policy(self) -> Policy {
+        Policy::custom(move |attempt| {
+            if attempt.previous().len() > MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if attempt
+                .url()
+                .host_str()
+                .and_then(|host| match_domain(self.domains.iter(), host))
+                == Some(self.domain.as_str())
+            {
+                attempt.follow()
+            } else {
+                attempt.stop()
+            }
+        })
+    }
+}
+
+/// A client for each configured host, along with a client for every other host.
+#[derive(Clone, Debug)]
+pub struct HttpClients {
+    default: Client,
+    hosts: BTreeMap<String, Client>,
+}
+
+impl HttpClients {
+    /// Builds the clients for a configuration, each sending `headers` unless they are replaced by
+    /// the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configured header or proxy is invalid, or if a client cannot be built.
+    pub fn new(config: &HttpConfig, headers: &HeaderMap) -> Result<Self, HttpConfigError> {
+        let domains = Arc::new(config.hosts.keys().cloned().collect::<Vec<_>>());
+        Ok(Self {
+            default: config
+                .defaults
+                .clone()
+                .merge(&config.overrides)
+                .build(headers.clone(), None)?,
+            hosts: config
+                .hosts
+                .keys()
+                .map(|host| {
+                    let scope = HostScope {
+                        domain: host.clone(),
+                        domains: Arc::clone(&domains),
+                    };
+                    Ok((
+                        host.clone(),
+                        config.for_host(host).build(headers.clone(), Some(scope))?,
+                    ))
+                })
+                .collect::<Result<_, HttpConfigError>>()?,
+        })
+    }
+
+    /// Returns the client to use for a URL.
+    ///
+    /// The client for a configured host does not follow redirects to other hosts, so use
+    /// [`HttpClients::send`] for requests that may be redirected.
+    pub fn get(&self, url: &url::Url) -> &Client {
+        url.host_str()
+            .and_then(|host| match_host(&self.hosts, host))
+            .unwrap_or(&self.default)
+    }
+
+    /// Sends the request built by `request` for a URL with the client for its host, retrying
+    /// transient failures.
+    ///
+    /// Redirects that a host's client stopped at are followed with the client for the host being
+    /// redirected to, so that each host only receives its own headers. If there are too many
+    /// redirects, the last redirect response is returned.
+    pub async fn send<F>(&self, url: &url::Url, request: F) -> reqwest::Result<Response>
+    where
+        F: Fn(&Client, url::Url) -> RequestBuilder,
+    {
+        let mut url = url.clone();
+        let mut redirects = 0;
+        loop {
+            let response = retry::send_with_retry(request(self.get(&url), url.clone())).await?;
+
+            let location = response
+                .status()
+                .is_redirection()
+                .then(|| response.headers().get(LOCATION)?.to_str().ok())
+                .flatten()
+                .and_then(|location| response.url().join(location).ok());
+
+            match location {
+                Some(location) if redirects < MAX_REDIRECTS => {
+                    url = location;
+                    redirects += 1;
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum HttpConfigError {
+    #[error(r#"Invalid header name "{0}""#)]
+    InvalidHeaderName(String),
+    #[error("Invalid value for the {0} header")]
+    InvalidHeaderValue(String),
+    #[error(transparent)]
+    Client(#[from] reqwest::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::rstest;
+
+    use super::{ClientConfig, HttpConfig, match_host};
+
+    #[rstest]
+    #[case::exact("example.com", Some("example.com"))]
+    #[case::subdomain("www.example.com", Some("example.com"))]
+    #[case::longest("cdn.downloads.example.com", Some("downloads.example.com"))]
+    #[case::suffix_without_dot("badexample.com", None)]
+    #[case::unrelated("example.org", None)]
+    fn host_matching(#[case] host: &str, #[case] expected: Option<&str>) {
+        let hosts = BTreeMap::from(
+            ["example.com", "downloads.example.com"].map(|domain| (domain.to_owned(), domain)),
+        );
+        assert_eq!(match_host(&hosts, host).copied(), expected);
+    }
+
+    #[test]
+    fn precedence() {
+        let config = HttpConfig {
+            defaults: ClientConfig {
+                user_agent: Some("default".to_owned()),
+                connect_timeout: Some(10),
+                ..ClientConfig::default()
+            },
+            hosts: BTreeMap::from([(
+                "example.com".to_owned(),
+                ClientConfig {
+                    user_agent: Some("host".to_owned()),
+                    read_timeout: Some(30),
+                    ..ClientConfig::default()
+                },
+            )]),
+            overrides: ClientConfig::default(),
+        }
+        .with_overrides(ClientConfig {
+            read_timeout: Some(60),
+            ..ClientConfig::default()
+        });
+
+        let host = config.for_host("example.com");
+        assert_eq!(host.user_agent.as_deref(), Some("host"));
+        assert_eq!(host.connect_timeout, Some(10));
+        assert_eq!(host.read_timeout, Some(60));
+
+        let other = config.for_host("example.org");
+        assert_eq!(other.user_agent.as_deref(), Some("default"));
+        assert_eq!(other.read_timeout, Some(60));
+    }
+}
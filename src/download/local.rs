@@ -0,0 +1,170 @@
+use std::{fmt, fs::File, io, str::FromStr};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use color_eyre::Result;
+use futures_util::future::try_join_all;
+use thiserror::Error;
+use winget_types::Sha256String;
+
+use super::DownloadedFile;
+use crate::manifests::Url;
+
+/// An installer that is already on disk, paired with the URL it will be published at.
+///
+/// This is parsed from `path=url`, where the URL may have the same overrides as a URL passed to
+/// `--urls`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocalInstaller {
+    path: Utf8PathBuf,
+    url: Url,
+}
+
+impl LocalInstaller {
+    #[inline]
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Opens and hashes the installer so that it can be analyzed as if it had been downloaded
+    /// from its URL.
+    pub fn open(self) -> io::Result<DownloadedFile> {
+        let mut file = File::open(&self.path)?;
+        let sha_256 = Sha256String::hash_from_reader(&mut file)?;
+
+        Ok(DownloadedFile {
+            file,
+            sha_256,
+            file_name: self
+                .path
+                .file_name()
+                .unwrap_or_else(|| self.path.as_str())
+                .to_owned(),
+            url: self.url,
+            last_modified: None,
+        })
+    }
+}
+
+/// Opens and hashes each local installer on a blocking thread so that they can be analyzed
+/// alongside downloaded installers.
+pub async fn open_local<I>(installers: I) -> Result<Vec<DownloadedFile>>
+where
+    I: IntoIterator<Item = LocalInstaller>,
+{
+    Ok(try_join_all(
+        installers
+            .into_iter()
+            .map(|installer| tokio::task::spawn_blocking(|| installer.open())),
+    )
+    .await?
+    .into_iter()
+    .collect::<io::Result<Vec<_>>>()?)
+}
+
+impl fmt::Display for LocalInstaller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.path, self.url)
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum LocalInstallerError {
+    #[error("Expected a local installer as PATH=URL")]
+    MissingUrl,
+    #[error("{0} is not a file")]
+    NotAFile(Utf8PathBuf),
+}
+
+impl FromStr for LocalInstaller {
+    type Err = LocalInstallerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Both paths and URLs may contain '=', so split at the first one that is followed by a
+        // valid URL
+        let (path, url) = s
+            .match_indices('=')
+            .filter(|&(index, _)| index > 0)
+            .find_map(|(index, _)| {
+                let url = s[index + 1..].parse::<Url>().ok()?;
+                Some((Utf8Path::new(&s[..index]), url))
+            })
+            .ok_or(LocalInstallerError::MissingUrl)?;
+
+        if !path.is_file() {
+            return Err(LocalInstallerError::NotAFile(path.to_path_buf()));
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            url,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use rstest::rstest;
+    use sha2::{Digest, Sha256};
+    use tempfile::NamedTempFile;
+    use winget_types::{Sha256String, installer::Architecture};
+
+    use super::{LocalInstaller, LocalInstallerError};
+
+    #[rstest]
+    #[case::plain("https://example.com/setup.exe", None)]
+    #[case::query("https://example.com/download?file=setup.exe", None)]
+    #[case::architecture("https://example.com/setup.exe|arm64", Some(Architecture::Arm64))]
+    fn parse(#[case] url: &str, #[case] architecture: Option<Architecture>) {
+        let file = NamedTempFile::new().unwrap();
+        let path = file.path().to_str().unwrap();
+
+        let local_installer = format!("{path}={url}").parse::<LocalInstaller>().unwrap();
+        assert_eq!(local_installer.path, path);
+        assert_eq!(local_installer.url.override_architecture(), architecture);
+        assert_eq!(
+            local_installer.url.as_str(),
+            url.split_once('|').map_or(url, |(url, _)| url)
+        );
+    }
+
+    #[test]
+    fn missing_url() {
+        assert_eq!(
+            "setup.exe".parse::<LocalInstaller>(),
+            Err(LocalInstallerError::MissingUrl)
+        );
+    }
+
+    #[test]
+    fn not_a_file() {
+        assert!(matches!(
+            "does-not-exist.exe=https://example.com/setup.exe".parse::<LocalInstaller>(),
+            Err(LocalInstallerError::NotAFile(_))
+        ));
+    }
+
+    #[test]
+    fn open() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"installer").unwrap();
+        let path = file.path().to_str().unwrap();
+
+        let downloaded_file = format!("{path}=https://example.com/setup.exe")
+            .parse::<LocalInstaller>()
+            .unwrap()
+            .open()
+            .unwrap();
+
+        assert_eq!(
+            downloaded_file.sha_256,
+            Sha256String::from_digest(&Sha256::digest(b"installer"))
+        );
+        assert_eq!(
+            downloaded_file.url.as_str(),
+            "https://example.com/setup.exe"
+        );
+        assert!(downloaded_file.last_modified.is_none());
+    }
+}
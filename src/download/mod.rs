@@ -1,21 +1,29 @@
+mod cache;
 mod downloader;
 mod downloads;
 mod file;
+mod http;
+mod local;
+mod retry;
+mod sniff;
 
 use std::{borrow::Cow, fmt};
 
+pub use cache::{CacheEntry, DownloadCache};
 use camino::Utf8Path;
 use const_format::formatcp;
 pub use downloader::Downloader;
 pub use downloads::Downloads;
 pub use file::DownloadedFile;
+pub use http::{ClientConfig, HttpClients, HttpConfig, HttpConfigError};
+pub use local::{LocalInstaller, open_local};
 use reqwest::{Client, ClientBuilder, Response, header::HeaderValue, redirect::Policy};
 use uuid::Uuid;
 use winget_types::installer::VALID_FILE_EXTENSIONS;
 
 use crate::{github::GITHUB_HOST, manifests::Url};
 
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Download(Url);
 
 impl Download {
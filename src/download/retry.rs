@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use reqwest::{RequestBuilder, Response, StatusCode};
+use tokio::time::sleep;
+use tracing::warn;
+
+/// The number of times a request is retried after a transient failure.
+pub const MAX_RETRIES: u32 = 3;
+
+/// The delay before the first retry, which doubles with each subsequent retry.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Returns how long to wait before the given retry, starting from 1.
+pub fn backoff(retry: u32) -> Duration {
+    INITIAL_BACKOFF * 2_u32.pow(retry.saturating_sub(1))
+}
+
+/// Returns `true` if a response with the given status may succeed if the request is retried.
+pub fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || matches!(
+            status,
+            StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS
+        )
+}
+
+/// Returns `true` if an error is caused by the connection rather than the request itself, such
+/// as a timeout, failing to connect, or the connection being reset while reading the body, so it
+/// may succeed if the request is retried.
+pub fn is_transient_error(error: &reqwest::Error) -> bool {
+    error.is_timeout()
+        || error.is_connect()
+        || error.is_body()
+        || error.status().is_some_and(is_transient_status)
+}
+
+/// Sends a request, retrying with exponential backoff if it fails with a transient error or
+/// status.
+pub async fn send_with_retry(request: RequestBuilder) -> reqwest::Result<Response> {
+    let mut retry = 0;
+
+    loop {
+        // Requests without a streaming body can always be cloned
+        let Some(attempt) = request.try_clone() else {
+            return request.send().await;
+        };
+
+        let result = attempt.send().await;
+
+        let is_transient = match &result {
+            Ok(response) => is_transient_status(response.status()),
+            Err(error) => is_transient_error(error),
+        };
+
+        if !is_transient || retry == MAX_RETRIES {
+            return result;
+        }
+
+        retry += 1;
+        let delay = backoff(retry);
+        match &result {
+            Ok(response) => warn!(
+                "{} returned {}. Retrying in {delay:?} ({retry}/{MAX_RETRIES})",
+                response.url(),
+                response.status()
+            ),
+            Err(error) => warn!("{error}. Retrying in {delay:?} ({retry}/{MAX_RETRIES})"),
+        }
+        sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+    use rstest::rstest;
+
+    use super::{backoff, is_transient_status};
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(2, 2)]
+    #[case(3, 4)]
+    fn exponential_backoff(#[case] retry: u32, #[case] seconds: u64) {
+        assert_eq!(backoff(retry), Duration::from_secs(seconds));
+    }
+
+    #[rstest]
+    #[case::internal_server_error(StatusCode::INTERNAL_SERVER_ERROR, true)]
+    #[case::bad_gateway(StatusCode::BAD_GATEWAY, true)]
+    #[case::too_many_requests(StatusCode::TOO_MANY_REQUESTS, true)]
+    #[case::request_timeout(StatusCode::REQUEST_TIMEOUT, true)]
+    #[case::not_found(StatusCode::NOT_FOUND, false)]
+    #[case::forbidden(StatusCode::FORBIDDEN, false)]
+    #[case::ok(StatusCode::OK, false)]
+    fn transient_status(#[case] status: StatusCode, #[case] expected: bool) {
+        assert_eq!(is_transient_status(status), expected);
+    }
+}
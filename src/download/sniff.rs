@@ -0,0 +1,93 @@
+use memchr::memmem;
+
+use crate::analysis::FileType;
+
+/// The number of bytes at the start of a download that are inspected to determine what it is.
+pub const SNIFF_LEN: usize = 512;
+
+/// The number of bytes at the start of an HTML page that are searched for its title.
+pub const TITLE_SEARCH_LEN: usize = 1 << 14;
+
+/// What the start of a download looks like.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Payload {
+    /// Starts with the signature of an installer format, such as `MZ`.
+    Installer,
+
+    /// An HTML page, such as a login wall or a download landing page.
+    Html,
+
+    /// Anything else, which is left for analysis to accept or reject.
+    Unknown,
+}
+
+impl Payload {
+    /// Determines what a download is from its first bytes, regardless of its `Content-Type`.
+    pub fn sniff(bytes: &[u8]) -> Self {
+        if FileType::has_signature(bytes) {
+            return Self::Installer;
+        }
+
+        // Skip a UTF-8 byte order mark and leading whitespace
+        let text = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        let text = text.trim_ascii_start();
+
+        let is_html = [b"<!doctype html".as_slice(), b"<html", b"<head", b"<body"]
+            .into_iter()
+            .any(|tag| {
+                text.get(..tag.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(tag))
+            });
+
+        if is_html { Self::Html } else { Self::Unknown }
+    }
+}
+
+/// Extracts the title of an HTML page, with its whitespace collapsed.
+pub fn html_title(html: &[u8]) -> Option<String> {
+    let lowercase = html.to_ascii_lowercase();
+
+    let start = memmem::find(&lowercase, b"<title")?;
+    let start = start + memchr::memchr(b'>', &lowercase[start..])? + 1;
+    let end = start + memmem::find(&lowercase[start..], b"</title")?;
+
+    let title = String::from_utf8_lossy(&html[start..end])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (!title.is_empty()).then_some(title)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{Payload, html_title};
+
+    #[rstest]
+    #[case::exe(b"MZ\x90\x00", Payload::Installer)]
+    #[case::msi(b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1", Payload::Installer)]
+    #[case::zip(b"PK\x03\x04", Payload::Installer)]
+    #[case::doctype(b"<!DOCTYPE html><html>", Payload::Html)]
+    #[case::whitespace(b"\r\n  <html lang=\"en\">", Payload::Html)]
+    #[case::bom(b"\xEF\xBB\xBF<!doctype html>", Payload::Html)]
+    #[case::plain_text(b"Hello", Payload::Unknown)]
+    #[case::empty(b"", Payload::Unknown)]
+    fn sniff(#[case] bytes: &[u8], #[case] expected: Payload) {
+        assert_eq!(Payload::sniff(bytes), expected);
+    }
+
+    #[rstest]
+    #[case::simple(b"<html><title>Sign in</title></html>", Some("Sign in"))]
+    #[case::attributes(
+        b"<HTML><TITLE id=\"t\">\n  Download   Example\n</TITLE>",
+        Some("Download Example")
+    )]
+    #[case::empty(b"<title> </title>", None)]
+    #[case::missing(b"<html><body></body></html>", None)]
+    #[case::unterminated(b"<title>Sign in", None)]
+    fn title(#[case] html: &[u8], #[case] expected: Option<&str>) {
+        assert_eq!(html_title(html).as_deref(), expected);
+    }
+}
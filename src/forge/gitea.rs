@@ -0,0 +1,141 @@
+use std::collections::BTreeSet;
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use url::Url;
+use winget_types::{
+    locale::{License, Publisher, ReleaseNotes, Tag},
+    url::{PackageUrl, PublisherSupportUrl, PublisherUrl, ReleaseNotesUrl},
+};
+
+use super::{ForgeError, Release, ReleaseMetadata};
+use crate::github::client::GitHubValues;
+
+/// Gets release values from the REST API of Gitea and its fork Forgejo, which Codeberg runs.
+///
+/// See <https://gitea.com/api/swagger>.
+pub struct Gitea {
+    client: Client,
+}
+
+impl Gitea {
+    #[inline]
+    pub const fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    fn endpoint<'a, I>(release: &Release, segments: I) -> Result<Url, ForgeError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut endpoint = Url::parse(&format!("https://{}/api/v1/repos", release.host))?;
+        endpoint
+            .path_segments_mut()
+            .map_err(|()| url::ParseError::RelativeUrlWithCannotBeABaseBase)?
+            .extend([release.owner.as_str(), release.repo.as_str()])
+            .extend(segments);
+        Ok(endpoint)
+    }
+}
+
+impl ReleaseMetadata for Gitea {
+    async fn get_release_values(&self, release: &Release) -> Result<GitHubValues, ForgeError> {
+        let repository_endpoint = Self::endpoint(release, [])?;
+        let release_endpoint = Self::endpoint(release, ["releases", "tags", &release.tag])?;
+
+        let (repository, gitea_release) = tokio::try_join!(
+            async {
+                self.client
+                    .get(repository_endpoint)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<Repository>()
+                    .await
+            },
+            async {
+                let response = self.client.get(release_endpoint).send().await?;
+                if response.status() == StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                response
+                    .error_for_status()?
+                    .json::<GiteaRelease>()
+                    .await
+                    .map(Some)
+            }
+        )?;
+
+        let owner_url = repository
+            .owner
+            .html_url
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| format!("https://{}/{}", release.host, repository.owner.login));
+
+        Ok(GitHubValues {
+            description: Some(repository.description).filter(|description| !description.is_empty()),
+            publisher: Publisher::new(if repository.owner.full_name.is_empty() {
+                repository.owner.login
+            } else {
+                repository.owner.full_name
+            })
+            .ok(),
+            publisher_url: owner_url.parse::<PublisherUrl>()?,
+            issues_url: if repository.has_issues {
+                format!("{}/issues", repository.html_url)
+                    .parse::<PublisherSupportUrl>()
+                    .ok()
+            } else {
+                None
+            },
+            license: repository
+                .licenses
+                .into_iter()
+                .next()
+                .and_then(|license| License::new(license).ok()),
+            license_url: None,
+            package_url: repository.html_url.parse::<PackageUrl>()?,
+            release_notes: gitea_release
+                .as_ref()
+                .and_then(|release| ReleaseNotes::new(release.body.trim()).ok()),
+            release_notes_url: gitea_release
+                .and_then(|release| release.html_url.parse::<ReleaseNotesUrl>().ok()),
+            topics: repository
+                .topics
+                .into_iter()
+                .flat_map(Tag::new)
+                .collect::<BTreeSet<_>>(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Repository {
+    #[serde(default)]
+    description: String,
+    html_url: String,
+    #[serde(default)]
+    has_issues: bool,
+    #[serde(default)]
+    topics: Vec<String>,
+    /// SPDX identifiers of the licenses detected in the repository, which only newer versions
+    /// return.
+    #[serde(default)]
+    licenses: Vec<String>,
+    owner: User,
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+    #[serde(default)]
+    full_name: String,
+    html_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GiteaRelease {
+    #[serde(default)]
+    body: String,
+    html_url: String,
+}
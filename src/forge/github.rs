@@ -0,0 +1,14 @@
+use super::{ForgeError, Release, ReleaseMetadata};
+use crate::github::client::{GitHub, GitHubValues};
+
+impl ReleaseMetadata for GitHub {
+    async fn get_release_values(&self, release: &Release) -> Result<GitHubValues, ForgeError> {
+        self.get_all_values()
+            .owner(&release.owner)
+            .repo(&release.repo)
+            .tag_name(&release.tag)
+            .send()
+            .await
+            .map_err(ForgeError::from)
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeSet;
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use url::Url;
+use winget_types::{
+    locale::{License, Publisher, ReleaseNotes, Tag},
+    url::{LicenseUrl, PackageUrl, PublisherSupportUrl, PublisherUrl, ReleaseNotesUrl},
+};
+
+use super::{ForgeError, Release, ReleaseMetadata};
+use crate::{
+    github::{client::GitHubValues, graphql::types::Html},
+    traits::FromHtml,
+};
+
+/// Gets release values from the REST API of GitLab.
+///
+/// See <https://docs.gitlab.com/api/projects/#get-a-single-project> and
+/// <https://docs.gitlab.com/api/releases/#get-a-release-by-a-tag-name>.
+pub struct GitLab {
+    client: Client,
+}
+
+impl GitLab {
+    #[inline]
+    pub const fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Returns the URL of a project in the API, which identifies projects by their full path.
+    fn project_endpoint(release: &Release) -> Result<Url, ForgeError> {
+        let mut endpoint = Url::parse(&format!("https://{}/api/v4/projects", release.host))?;
+        endpoint
+            .path_segments_mut()
+            .map_err(|()| url::ParseError::RelativeUrlWithCannotBeABaseBase)?
+            .push(&format!("{}/{}", release.owner, release.repo));
+        Ok(endpoint)
+    }
+}
+
+impl ReleaseMetadata for GitLab {
+    async fn get_release_values(&self, release: &Release) -> Result<GitHubValues, ForgeError> {
+        let mut project_endpoint = Self::project_endpoint(release)?;
+        let mut release_endpoint = project_endpoint.clone();
+        project_endpoint
+            .query_pairs_mut()
+            .append_pair("license", "true");
+        release_endpoint
+            .path_segments_mut()
+            .map_err(|()| url::ParseError::RelativeUrlWithCannotBeABaseBase)?
+            .extend(["releases", &release.tag]);
+        release_endpoint
+            .query_pairs_mut()
+            .append_pair("include_html_description", "true");
+
+        let (project, gitlab_release) = tokio::try_join!(
+            async {
+                self.client
+                    .get(project_endpoint)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<Project>()
+                    .await
+            },
+            async {
+                let response = self.client.get(release_endpoint).send().await?;
+                if response.status() == StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                response
+                    .error_for_status()?
+                    .json::<GitLabRelease>()
+                    .await
+                    .map(Some)
+            }
+        )?;
+
+        Ok(GitHubValues {
+            description: project
+                .description
+                .filter(|description| !description.is_empty()),
+            publisher: Publisher::new(project.namespace.name).ok(),
+            publisher_url: project.namespace.web_url.parse::<PublisherUrl>()?,
+            issues_url: if project.issues_enabled {
+                format!("{}/-/issues", project.web_url)
+                    .parse::<PublisherSupportUrl>()
+                    .ok()
+            } else {
+                None
+            },
+            license: project
+                .license
+                .and_then(|license| License::new(license.name).ok()),
+            license_url: project
+                .license_url
+                .and_then(|url| url.parse::<LicenseUrl>().ok()),
+            package_url: project.web_url.parse::<PackageUrl>()?,
+            release_notes: gitlab_release
+                .as_ref()
+                .and_then(|release| release.description_html.as_deref())
+                .and_then(|html| ReleaseNotes::from_html(&Html::new(html))),
+            release_notes_url: gitlab_release
+                .and_then(|release| release.links.url.parse::<ReleaseNotesUrl>().ok()),
+            topics: project
+                .topics
+                .into_iter()
+                .flat_map(Tag::new)
+                .collect::<BTreeSet<_>>(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Project {
+    description: Option<String>,
+    web_url: String,
+    #[serde(default)]
+    topics: Vec<String>,
+    namespace: Namespace,
+    #[serde(default)]
+    issues_enabled: bool,
+    license: Option<ProjectLicense>,
+    license_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Namespace {
+    name: String,
+    web_url: String,
+}
+
+#[derive(Deserialize)]
+struct ProjectLicense {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabRelease {
+    description_html: Option<String>,
+    #[serde(rename = "_links")]
+    links: ReleaseLinks,
+}
+
+#[derive(Deserialize)]
+struct ReleaseLinks {
+    #[serde(rename = "self")]
+    url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GitLab;
+    use crate::forge::Release;
+
+    #[test]
+    fn project_path_is_encoded() {
+        let release = Release {
+            host: "gitlab.com".to_owned(),
+            owner: "group/subgroup".to_owned(),
+            repo: "repo".to_owned(),
+            tag: "v1.0".to_owned(),
+        };
+        assert_eq!(
+            GitLab::project_endpoint(&release).unwrap().as_str(),
+            "https://gitlab.com/api/v4/projects/group%2Fsubgroup%2Frepo"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+mod gitea;
+mod github;
+mod gitlab;
+
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use serde::Deserialize;
+use thiserror::Error;
+use url::Url;
+use winget_types::url::DecodedUrl;
+
+pub use gitea::Gitea;
+pub use gitlab::GitLab;
+
+use crate::{
+    download::{HttpClients, HttpConfig, HttpConfigError},
+    github::{
+        GitHubError,
+        client::{GitHub, GitHubValues},
+    },
+    token::default_headers,
+};
+
+/// Fills in locale values, such as the license and release notes, from the project that
+/// published a release.
+pub trait ReleaseMetadata {
+    /// Gets the values for a release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project cannot be fetched from the forge.
+    async fn get_release_values(&self, release: &Release) -> Result<GitHubValues, ForgeError>;
+}
+
+/// The software that a host runs, which determines its API and the layout of its release URLs.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeKind {
+    #[serde(skip)]
+    GitHub,
+    GitLab,
+    #[serde(alias = "forgejo")]
+    Gitea,
+}
+
+impl ForgeKind {
+    /// Hosts of public forges that are recognised without configuration.
+    const KNOWN_HOSTS: [(&'static str, Self); 3] = [
+        ("gitlab.com", Self::GitLab),
+        ("codeberg.org", Self::Gitea),
+        ("gitea.com", Self::Gitea),
+    ];
+
+    /// Parses the project and tag of a release from the URL of one of its assets.
+    ///
+    /// GitHub and Gitea serve assets from `/{owner}/{repo}/releases/download/{tag}/{file}`.
+    /// GitLab projects can be nested in groups, so everything before `/-/` is the project path,
+    /// followed by `releases/{tag}/downloads/{file}` or `archive/{tag}/{file}`.
+    pub fn parse_release(self, url: &Url) -> Option<Release> {
+        let host = url.host_str()?;
+        let segments = url.path_segments()?.collect::<Vec<_>>();
+
+        let (owner, repo, tag) = match self {
+            Self::GitHub | Self::Gitea => match segments.as_slice() {
+                [owner, repo, "releases", "download", tag @ .., _file] if !tag.is_empty() => {
+                    ((*owner).to_owned(), (*repo).to_owned(), tag.join("/"))
+                }
+                _ => return None,
+            },
+            Self::GitLab => {
+                let separator = segments.iter().position(|&segment| segment == "-")?;
+                let (project, rest) = segments.split_at(separator);
+                let [namespace @ .., repo] = project else {
+                    return None;
+                };
+                let tag = match &rest[1..] {
+                    ["releases", rest @ ..] => rest
+                        .iter()
+                        .take_while(|&&segment| segment != "downloads")
+                        .join("/"),
+                    ["archive", tag, _file] => (*tag).to_owned(),
+                    _ => return None,
+                };
+                if namespace.is_empty() || tag.is_empty() {
+                    return None;
+                }
+                (namespace.join("/"), (*repo).to_owned(), tag)
+            }
+        };
+
+        Some(Release {
+            host: host.to_owned(),
+            owner,
+            repo,
+            tag,
+        })
+    }
+}
+
+/// A release of a project on a forge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Release {
+    pub host: String,
+    /// The owner of the project, which is the full group path for nested GitLab projects.
+    pub owner: String,
+    pub repo: String,
+    pub tag: String,
+}
+
+/// Selects the forge that hosts a release by the host of its URL.
+#[derive(Clone)]
+pub struct Forges {
+    /// A GitHub client for each GitHub instance that a token is available for.
+    github: BTreeMap<String, GitHub>,
+    clients: HttpClients,
+    hosts: BTreeMap<String, ForgeKind>,
+}
+
+impl Forges {
+    /// Creates a selector that recognises the upstream GitHub instance and github.com, well-known
+    /// public forges, and any self-hosted forges in `hosts`.
+    ///
+    /// GitHub is only recognised if a GitHub client is given, as its API requires a token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP configuration is invalid or a GitHub client cannot be built.
+    pub fn new(
+        github: Option<GitHub>,
+        http: &HttpConfig,
+        hosts: &BTreeMap<String, ForgeKind>,
+    ) -> Result<Self, ForgeError> {
+        let mut github_clients = BTreeMap::new();
+        if let Some(github) = github {
+            if let Some(github_com) = github.github_com()? {
+                github_clients.insert(github_com.host().to_owned(), github_com);
+            }
+            github_clients.insert(github.host().to_owned(), github);
+        }
+
+        Ok(Self {
+            github: github_clients,
+            clients: HttpClients::new(http, &default_headers(None))?,
+            hosts: ForgeKind::KNOWN_HOSTS
+                .into_iter()
+                .map(|(host, kind)| (host.to_owned(), kind))
+                .chain(hosts.iter().map(|(host, &kind)| (host.clone(), kind)))
+                .collect(),
+        })
+    }
+
+    /// Finds the first URL that is a release asset on a known forge and gets the values for that
+    /// release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project cannot be fetched from the forge.
+    pub async fn get_release_values<'url, I>(
+        &self,
+        urls: I,
+    ) -> Result<Option<GitHubValues>, ForgeError>
+    where
+        I: IntoIterator<Item = &'url DecodedUrl>,
+    {
+        let Some((kind, release, client)) = urls.into_iter().find_map(|url| {
+            let host = url.host_str()?;
+            let kind = if self.github.contains_key(host) {
+                ForgeKind::GitHub
+            } else {
+                *self.hosts.get(host)?
+            };
+            Some((kind, kind.parse_release(url)?, self.clients.get(url)))
+        }) else {
+            return Ok(None);
+        };
+
+        let values = match kind {
+            ForgeKind::GitHub => {
+                self.github[&release.host]
+                    .get_release_values(&release)
+                    .await
+            }
+            ForgeKind::GitLab => {
+                GitLab::new(client.clone())
+                    .get_release_values(&release)
+                    .await
+            }
+            ForgeKind::Gitea => {
+                Gitea::new(client.clone())
+                    .get_release_values(&release)
+                    .await
+            }
+        }?;
+
+        Ok(Some(values))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ForgeError {
+    #[error(transparent)]
+    GitHub(#[from] GitHubError),
+    #[error(transparent)]
+    HttpConfig(#[from] HttpConfigError),
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    #[error(transparent)]
+    UrlParse(#[from] url::ParseError),
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use url::Url;
+
+    use super::{ForgeKind, Release};
+
+    #[rstest]
+    #[case::github(
+        ForgeKind::GitHub,
+        "https://github.com/owner/repo/releases/download/v1.2.3/setup.exe",
+        Some(("owner", "repo", "v1.2.3"))
+    )]
+    #[case::github_tag_with_slash(
+        ForgeKind::GitHub,
+        "https://github.com/owner/repo/releases/download/app/v1.2.3/setup.exe",
+        Some(("owner", "repo", "app/v1.2.3"))
+    )]
+    #[case::github_not_a_release(
+        ForgeKind::GitHub,
+        "https://github.com/owner/repo/raw/main/setup.exe",
+        None
+    )]
+    #[case::gitea(
+        ForgeKind::Gitea,
+        "https://codeberg.org/owner/repo/releases/download/1.0/setup.msi",
+        Some(("owner", "repo", "1.0"))
+    )]
+    #[case::gitlab_release(
+        ForgeKind::GitLab,
+        "https://gitlab.com/group/repo/-/releases/v2.0/downloads/setup.exe",
+        Some(("group", "repo", "v2.0"))
+    )]
+    #[case::gitlab_subgroup(
+        ForgeKind::GitLab,
+        "https://gitlab.com/group/subgroup/repo/-/releases/v2.0/downloads/bin/setup.exe",
+        Some(("group/subgroup", "repo", "v2.0"))
+    )]
+    #[case::gitlab_archive(
+        ForgeKind::GitLab,
+        "https://gitlab.com/group/repo/-/archive/v2.0/repo-v2.0.zip",
+        Some(("group", "repo", "v2.0"))
+    )]
+    #[case::gitlab_not_a_release(
+        ForgeKind::GitLab,
+        "https://gitlab.com/group/repo/-/raw/main/setup.exe",
+        None
+    )]
+    fn parse_release(
+        #[case] kind: ForgeKind,
+        #[case] url: &str,
+        #[case] expected: Option<(&str, &str, &str)>,
+    ) {
+        let url = Url::parse(url).unwrap();
+        assert_eq!(
+            kind.parse_release(&url),
+            expected.map(|(owner, repo, tag)| Release {
+                host: url.host_str().unwrap().to_owned(),
+                owner: owner.to_owned(),
+                repo: repo.to_owned(),
+                tag: tag.to_owned(),
+            })
+        );
+    }
+}
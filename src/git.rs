@@ -0,0 +1,513 @@
+use std::{
+    collections::BTreeSet,
+    ffi::OsStr,
+    io::{self, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use anstream::println;
+use camino::{Utf8Path, Utf8PathBuf};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tempfile::{NamedTempFile, TempPath};
+use thiserror::Error;
+use winget_types::{PackageIdentifier, PackageVersion};
+
+use crate::{
+    github::{
+        Upstream,
+        utils::{
+            PackagePath, branch_name, commit_title,
+            pull_request::{Change, Changes},
+        },
+    },
+    manifests::{Manifests, ManifestsError},
+    update_state::UpdateState,
+};
+
+/// A local clone of the upstream repository that manifests are committed into instead of opening
+/// a pull request.
+///
+/// Commits are built with Git's plumbing commands in a separate index, so the working tree and the
+/// checked out branch are left untouched. This allows many packages to be committed one after the
+/// other, each to its own branch.
+pub struct LocalRepository {
+    path: Utf8PathBuf,
+}
+
+impl LocalRepository {
+    const INDEX_FILE_PREFIX: &'static str = "komac-index";
+
+    /// Opens the repository that contains `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Git cannot be run or `path` is not inside a Git repository.
+    pub fn open<P: AsRef<Utf8Path>>(path: P) -> Result<Self, GitError> {
+        let path = path.as_ref();
+        let top_level = Self::run(
+            Command::new("git")
+                .arg("-C")
+                .arg(path)
+                .args(["rev-parse", "--show-toplevel"]),
+            None,
+        )
+        .map_err(|_| GitError::NotARepository(path.to_owned()))?;
+
+        Ok(Self {
+            path: Utf8PathBuf::from(top_level),
+        })
+    }
+
+    /// Returns the versions of a package in the upstream revision of the repository.
+    ///
+    /// Like [`GitHub::get_versions`](crate::github::client::GitHub::get_versions), directories
+    /// that contain other directories are packages nested under this one and are not versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the package does not exist in the repository.
+    pub fn get_versions(
+        &self,
+        identifier: &PackageIdentifier,
+    ) -> Result<BTreeSet<PackageVersion>, GitError> {
+        let path = PackagePath::new(identifier, None, None);
+        let tree = self
+            .git(
+                [
+                    "ls-tree",
+                    "-r",
+                    "-t",
+                    "-z",
+                    &format!("{}:{path}", Upstream::get().rev()),
+                ],
+                None,
+            )
+            .map_err(|_| GitError::PackageNonExistent(identifier.clone()))?;
+
+        let versions = parse_versions(&tree);
+
+        if versions.is_empty() {
+            Err(GitError::PackageNonExistent(identifier.clone()))
+        } else {
+            Ok(versions)
+        }
+    }
+
+    /// Returns the manifests of a package version in the upstream revision of the repository.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version does not exist in the repository or its manifests cannot be
+    /// read.
+    pub fn get_manifests(
+        &self,
+        identifier: &PackageIdentifier,
+        version: &PackageVersion,
+    ) -> Result<Manifests, GitError> {
+        let path = PackagePath::new(identifier, Some(version), None);
+        let tree = format!("{}:{path}", Upstream::get().rev());
+        let files = self
+            .git(["ls-tree", "-z", "--name-only", &tree], None)?
+            .split_terminator('\0')
+            .map(|name| {
+                let text = self.git(["show", &format!("{tree}/{name}")], None)?;
+                Ok((name.to_owned(), text))
+            })
+            .collect::<Result<Vec<_>, GitError>>()?;
+
+        Ok(Manifests::from_files(identifier, &path, files)?)
+    }
+
+    /// Commits a new version of a package to a new branch, removing `replace_version` if it is
+    /// given, and optionally pushes the branch to a remote.
+    ///
+    /// The branch is created from the upstream revision with the same name and commit message as
+    /// a pull request would have.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any Git command fails.
+    pub fn add_version(
+        &self,
+        identifier: &PackageIdentifier,
+        version: &PackageVersion,
+        versions: Option<&BTreeSet<PackageVersion>>,
+        changes: &Changes,
+        replace_version: Option<&PackageVersion>,
+        push: Option<&str>,
+    ) -> Result<LocalCommit, GitError> {
+        let base = self.git(["rev-parse", "--verify", Upstream::get().rev()], None)?;
+        let branch_name = branch_name(identifier, version);
+        let commit_title = commit_title(identifier, version, UpdateState::get(version, versions));
+
+        let index = self.index_file()?;
+        let replace_path =
+            replace_version.map(|version| PackagePath::new(identifier, Some(version), None));
+        let commit =
+            self.commit_changes(&index, &base, &commit_title, changes, replace_path.as_ref())?;
+
+        self.git(
+            [
+                "update-ref",
+                &format!("refs/heads/{branch_name}"),
+                &commit,
+                "",
+            ],
+            None,
+        )?;
+
+        if let Some(remote) = push {
+            self.git(["push", "--quiet", remote, &branch_name], None)?;
+        }
+
+        Ok(LocalCommit {
+            repository: self.path.clone(),
+            branch_name,
+            commit,
+            remote: push.map(str::to_owned),
+        })
+    }
+
+    /// Writes a commit on top of `base` without touching the working tree, returning its hash.
+    fn commit_changes(
+        &self,
+        index: &Path,
+        base: &str,
+        message: &str,
+        changes: &Changes,
+        replace_path: Option<&PackagePath>,
+    ) -> Result<String, GitError> {
+        let with_index = |command: &mut Command| {
+            command.env("GIT_INDEX_FILE", index);
+        };
+
+        self.git_with(["read-tree", base], None, with_index)?;
+
+        if let Some(replace_path) = replace_path {
+            self.git_with(
+                [
+                    "rm",
+                    "--cached",
+                    "-r",
+                    "--quiet",
+                    "--ignore-unmatch",
+                    "--",
+                    replace_path.as_str(),
+                ],
+                None,
+                with_index,
+            )?;
+        }
+
+        for Change { path, manifest } in changes.iter() {
+            let blob = self.git(["hash-object", "-w", "--stdin"], Some(manifest))?;
+            self.git_with(
+                [
+                    "update-index",
+                    "--add",
+                    "--cacheinfo",
+                    &format!("100644,{blob},{path}"),
+                ],
+                None,
+                with_index,
+            )?;
+        }
+
+        let tree = self.git_with(["write-tree"], None, with_index)?;
+        self.git(["commit-tree", &tree, "-p", base, "-m", message], None)
+    }
+
+    /// Creates a temporary index in the Git directory to build a commit in, which is deleted when
+    /// it is dropped.
+    ///
+    /// Each commit gets its own index so that concurrent runs against the same clone cannot
+    /// overwrite each other's staged trees.
+    fn index_file(&self) -> Result<TempPath, GitError> {
+        let git_dir = self.git(["rev-parse", "--absolute-git-dir"], None)?;
+        NamedTempFile::with_prefix_in(Self::INDEX_FILE_PREFIX, git_dir)
+            .map(NamedTempFile::into_temp_path)
+            .map_err(GitError::TempIndex)
+    }
+
+    fn git<I, S>(&self, args: I, stdin: Option<&str>) -> Result<String, GitError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.git_with(args, stdin, |_| {})
+    }
+
+    fn git_with<I, S, F>(
+        &self,
+        args: I,
+        stdin: Option<&str>,
+        configure: F,
+    ) -> Result<String, GitError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+        F: FnOnce(&mut Command),
+    {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.path).args(args);
+        configure(&mut command);
+        Self::run(&mut command, stdin)
+    }
+
+    /// Runs a Git command, returning its standard output with surrounding whitespace removed.
+    fn run(command: &mut Command, stdin: Option<&str>) -> Result<String, GitError> {
+        let mut child = command
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(GitError::Spawn)?;
+
+        if let Some(stdin) = stdin
+            && let Some(mut child_stdin) = child.stdin.take()
+        {
+            child_stdin
+                .write_all(stdin.as_bytes())
+                .map_err(GitError::Spawn)?;
+        }
+
+        let output = child.wait_with_output().map_err(GitError::Spawn)?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        } else {
+            Err(GitError::Command {
+                command: command
+                    .get_args()
+                    .skip(2)
+                    .map(OsStr::to_string_lossy)
+                    .join(" "),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            })
+        }
+    }
+}
+
+/// Returns the versions in the NUL-separated output of `git ls-tree -r -t -z` for a package's
+/// directory.
+fn parse_versions(tree: &str) -> BTreeSet<PackageVersion> {
+    const SEPARATOR: char = '/';
+
+    tree.split_terminator('\0')
+        .filter_map(|entry| {
+            // Each entry is `<mode> <type> <object>\t<path>`
+            let (info, path) = entry.split_once('\t')?;
+            let is_tree = info.split(' ').nth(1) == Some("tree");
+            Some((is_tree, path))
+        })
+        .filter(|(_, path)| path.matches(SEPARATOR).count() == 1)
+        .chunk_by(|(_, path)| {
+            path.split_once(SEPARATOR)
+                .map_or(*path, |(version, _rest)| version)
+        })
+        .into_iter()
+        .filter_map(|(version, mut group)| {
+            group
+                .all(|(is_tree, _)| !is_tree)
+                .then(|| version.parse::<PackageVersion>().ok())?
+        })
+        .collect()
+}
+
+/// A version committed to a local repository.
+pub struct LocalCommit {
+    repository: Utf8PathBuf,
+    branch_name: String,
+    commit: String,
+    remote: Option<String>,
+}
+
+impl LocalCommit {
+    pub fn print_success(&self) {
+        println!(
+            "{} committed {} to {} in {}",
+            "Successfully".green(),
+            self.commit.get(..12).unwrap_or(&self.commit).yellow(),
+            self.branch_name.blue(),
+            self.repository.blue()
+        );
+
+        if let Some(remote) = &self.remote {
+            println!("Pushed {} to {}", self.branch_name.blue(), remote.blue());
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum GitError {
+    #[error("{0} is not inside a Git repository")]
+    NotARepository(Utf8PathBuf),
+    #[error("{0} does not exist in the local repository")]
+    PackageNonExistent(PackageIdentifier),
+    #[error("Failed to run Git. Please check that it is installed and on the PATH")]
+    Spawn(#[source] io::Error),
+    #[error("Failed to create a temporary Git index")]
+    TempIndex(#[source] io::Error),
+    #[error("`git {command}` failed: {stderr}")]
+    Command { command: String, stderr: String },
+    #[error(transparent)]
+    Manifests(#[from] ManifestsError),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fs, process::Command};
+
+    use camino::Utf8Path;
+    use winget_types::{
+        PackageIdentifier, PackageVersion, VersionManifest, installer::InstallerManifest,
+        locale::DefaultLocaleManifest,
+    };
+
+    use super::{LocalRepository, parse_versions};
+    use crate::{
+        github::utils::pull_request::{Change, Changes},
+        manifests::Manifests,
+    };
+
+    #[test]
+    fn versions_exclude_nested_packages() {
+        let tree = [
+            "040000 tree a\t1.0.0",
+            "100644 blob b\t1.0.0/Package.yaml",
+            "040000 tree c\t2.0.0",
+            "100644 blob d\t2.0.0/Package.yaml",
+            "040000 tree e\tNested",
+            "040000 tree f\tNested/1.0.0",
+            "100644 blob g\tNested/1.0.0/Package.Nested.yaml",
+        ]
+        .join("\0");
+
+        assert_eq!(
+            parse_versions(&tree),
+            BTreeSet::from(["1.0.0".parse().unwrap(), "2.0.0".parse().unwrap()])
+        );
+    }
+
+    fn git(path: &Utf8Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(path)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    fn init(path: &Utf8Path) {
+        git(path, &["init", "--quiet"]);
+        git(path, &["config", "user.name", "Komac"]);
+        git(path, &["config", "user.email", "komac@example.com"]);
+        git(
+            path,
+            &["commit", "--quiet", "--allow-empty", "-m", "Initial commit"],
+        );
+    }
+
+    #[test]
+    fn reads_package_from_upstream_revision() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = Utf8Path::from_path(temp_dir.path()).unwrap();
+        init(path);
+
+        let identifier = "Package.Identifier".parse::<PackageIdentifier>().unwrap();
+        let version = "1.2.3".parse::<PackageVersion>().unwrap();
+        let mut manifests = Manifests {
+            installer: InstallerManifest::default(),
+            default_locale: DefaultLocaleManifest::default(),
+            locales: Vec::new(),
+            version: VersionManifest::new(
+                identifier.clone(),
+                version.clone(),
+                "en-US".parse().unwrap(),
+            ),
+        };
+        manifests.installer.package_identifier = identifier.clone();
+        manifests.installer.package_version = version.clone();
+        manifests.default_locale.package_identifier = identifier.clone();
+        manifests.default_locale.package_version = version.clone();
+        manifests.default_locale.package_locale = "en-US".parse().unwrap();
+
+        for Change {
+            path: file,
+            manifest,
+        } in manifests.create(&identifier, &version, None).iter()
+        {
+            let file = path.join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, manifest).unwrap();
+        }
+        git(path, &["add", "--all"]);
+        git(path, &["commit", "--quiet", "-m", "Add package"]);
+
+        let repository = LocalRepository::open(path).unwrap();
+        let package = repository.get_package(&identifier).unwrap();
+
+        assert_eq!(package.latest_version(), Some(&version));
+        let read = package.manifests.unwrap();
+        assert_eq!(read.installer, manifests.installer);
+        assert_eq!(
+            read.default_locale.package_locale,
+            manifests.default_locale.package_locale
+        );
+        assert_eq!(read.version, manifests.version);
+    }
+
+    #[test]
+    fn add_version_leaves_working_tree_untouched() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = Utf8Path::from_path(temp_dir.path()).unwrap();
+        let git = |args: &[&str]| git(path, args);
+        init(path);
+
+        let repository = LocalRepository::open(path).unwrap();
+        let identifier = "Package.Identifier".parse::<PackageIdentifier>().unwrap();
+        let version = "1.2.3".parse::<PackageVersion>().unwrap();
+        let changes = Changes::new([Change {
+            path: "manifests/p/Package/Identifier/1.2.3/Package.Identifier.yaml".to_owned(),
+            manifest: "PackageIdentifier: Package.Identifier\r\n".to_owned(),
+        }]);
+
+        let commit = repository
+            .add_version(&identifier, &version, None, &changes, None, None)
+            .unwrap();
+
+        assert_eq!(
+            git(&["log", "-1", "--format=%s", &commit.branch_name]).trim(),
+            "New package: Package.Identifier version 1.2.3"
+        );
+        assert_eq!(
+            git(&["rev-parse", &commit.branch_name]).trim(),
+            commit.commit
+        );
+        assert_eq!(
+            git(&["ls-tree", "-r", "--name-only", &commit.branch_name]).trim(),
+            changes.iter().next().unwrap().path()
+        );
+
+        // The package only exists on the new branch, not in the upstream revision
+        assert!(repository.get_versions(&identifier).is_err());
+        assert!(git(&["status", "--porcelain"]).is_empty());
+
+        // The temporary index is removed once the commit is written
+        assert!(!fs::read_dir(path.join(".git")).unwrap().any(|entry| {
+            entry
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with(LocalRepository::INDEX_FILE_PREFIX)
+        }));
+    }
+}
@@ -1,42 +1,51 @@
-use std::{borrow::Cow, collections::BTreeSet, num::NonZeroU32, str::FromStr};
+use std::{
+    borrow::Cow, collections::BTreeSet, env, num::NonZeroU32, str::FromStr, sync::Arc,
+    time::Duration,
+};
 
 use bon::bon;
 use color_eyre::eyre::eyre;
-use cynic::{GraphQlResponse, Id, MutationBuilder, QueryBuilder, http::ReqwestExt};
-use futures_util::future::OptionFuture;
+use cynic::{
+    GraphQlResponse, Id, MutationBuilder, Operation, QueryBuilder, http::CynicReqwestError,
+};
 use indexmap::IndexMap;
 use indicatif::ProgressBar;
-use itertools::Itertools;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response};
 use secrecy::SecretString;
-use serde::de::DeserializeOwned;
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::time::sleep;
+use tracing::warn;
 use url::Url;
 use winget_types::{
-    DefaultLocaleManifest, InstallerManifest, LocaleManifest, Manifest, ManifestType,
-    ManifestTypeWithLocale, PackageIdentifier, PackageVersion, VersionManifest,
+    Manifest, ManifestTypeWithLocale, PackageIdentifier, PackageVersion,
     locale::{License, Publisher, ReleaseNotes, Tag},
     url::{DecodedUrl, LicenseUrl, PackageUrl, PublisherSupportUrl, PublisherUrl, ReleaseNotesUrl},
 };
 
-use super::{GitHubError, graphql::create_pull_request};
+use super::{
+    GITHUB_HOST, GitHubError,
+    graphql::create_pull_request,
+    rate_limit::{self, RateLimits, Resource},
+    upstream::graphql_url,
+};
 use crate::{
     commands::{cleanup::MergeState, utils::SPINNER_TICK_RATE},
     github::{
-        MICROSOFT, WINGET_PKGS, WINGET_PKGS_FULL_NAME,
+        Upstream,
         graphql::{
-            GRAPHQL_URL,
             create_commit::{FileAddition, FileDeletion},
             create_ref::{CreateRef, CreateRefVariables, Ref as CreateBranchRef},
             get_all_values::{GetAllValues, GetAllValuesGitObject, GetAllValuesVariables, Tree},
             get_branches::{GetBranches, GetBranchesVariables, PullRequest, RefConnection},
             get_directory_content::GetDirectoryContentVariables,
             get_directory_content_with_text::{GetDirectoryContentWithText, TreeEntry},
+            get_pull_request_head,
             get_repository_info::{GetRepositoryInfo, RepositoryVariables, TargetGitObject},
             types::GitObjectId,
             update_refs::{RefUpdate, UpdateRefs, UpdateRefsInput},
         },
         utils::{
-            CommitTitle, PackagePath, branch_name, commit_title, is_manifest_file,
+            CommitTitle, PackagePath, branch_name, commit_title,
             pull_request::{Change, Changes},
             pull_request_body,
         },
@@ -48,20 +57,119 @@
 };
 
 #[derive(Clone)]
-#[repr(transparent)]
-pub struct GitHub(pub(super) Client);
+pub struct GitHub {
+    pub(super) client: Client,
+    pub(super) rate_limits: Arc<RateLimits>,
+    host: String,
+}
 
 #[bon]
 impl GitHub {
+    /// The environment variable holding a token for github.com, used to get the values of
+    /// releases on github.com when the upstream repository is on GitHub Enterprise Server.
+    const GITHUB_COM_TOKEN: &'static str = "KOMAC_GITHUB_COM_TOKEN";
+
+    /// Creates a client for the GitHub instance that hosts the upstream repository.
     pub fn new<T>(token: T) -> Result<Self, GitHubError>
     where
         T: AsRef<SecretString>,
     {
-        Ok(Self(
-            Client::builder()
-                .default_headers(default_headers(Some(token.as_ref())))
+        Self::for_host(token.as_ref(), Upstream::get().host())
+    }
+
+    /// Creates a client for the GitHub instance at `host`.
+    fn for_host(token: &SecretString, host: &str) -> Result<Self, GitHubError> {
+        Ok(Self {
+            client: Client::builder()
+                .default_headers(default_headers(Some(token)))
                 .build()?,
-        ))
+            rate_limits: Arc::default(),
+            host: host.to_owned(),
+        })
+    }
+
+    /// Returns the host of the GitHub instance that this client talks to.
+    #[inline]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns a client for github.com, where most releases are published.
+    ///
+    /// This is the same client if it already talks to github.com. Otherwise, as a token is only
+    /// ever sent to the instance it is for, a client for github.com is only available if a token
+    /// for it is set in `KOMAC_GITHUB_COM_TOKEN`.
+    pub fn github_com(&self) -> Result<Option<Self>, GitHubError> {
+        if self.host == GITHUB_HOST {
+            return Ok(Some(self.clone()));
+        }
+
+        env::var(Self::GITHUB_COM_TOKEN)
+            .ok()
+            .filter(|token| !token.is_empty())
+            .map(|token| Self::for_host(&SecretString::from(token), GITHUB_HOST))
+            .transpose()
+    }
+
+    /// Sends a REST API request, waiting first if the rate limit is nearly exhausted.
+    pub(super) async fn rest(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        self.send(request, Resource::Core).await
+    }
+
+    /// Runs a GraphQL operation, waiting first if the rate limit is nearly exhausted.
+    pub(super) async fn graphql<ResponseData, Vars>(
+        &self,
+        operation: Operation<ResponseData, Vars>,
+    ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+    where
+        ResponseData: DeserializeOwned + 'static,
+        Vars: Serialize,
+    {
+        let response = self
+            .send(
+                self.client.post(graphql_url(&self.host)).json(&operation),
+                Resource::GraphQl,
+            )
+            .await?;
+
+        // Deserialise the same way as cynic so that errors are reported in the same way
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json().await?)
+        } else {
+            let text = response.text().await?;
+            serde_json::from_str(&text).map_err(|_| CynicReqwestError::ErrorResponse(status, text))
+        }
+    }
+
+    /// Sends a request, retrying if it hits a secondary rate limit and recording the remaining
+    /// budget from its response.
+    async fn send(&self, request: RequestBuilder, resource: Resource) -> reqwest::Result<Response> {
+        let mut retry = 0;
+
+        loop {
+            self.rate_limits.wait(resource).await;
+
+            // Requests without a streaming body can always be cloned
+            let Some(attempt) = request.try_clone() else {
+                return request.send().await;
+            };
+
+            let response = attempt.send().await?;
+            self.rate_limits.update(response.headers());
+
+            match rate_limit::retry_delay(&response) {
+                Some(delay) if retry < rate_limit::MAX_RETRIES => {
+                    retry += 1;
+                    warn!(
+                        "Hit a GitHub {resource} API rate limit. Retrying in {delay:?} ({retry}/{})",
+                        rate_limit::MAX_RETRIES
+                    );
+                    sleep(delay).await;
+                }
+                _ => return Ok(response),
+            }
+        }
     }
 
     pub async fn get_manifests(
@@ -71,74 +179,30 @@ pub async fn get_manifests(
     ) -> Result<Manifests, GitHubError> {
         let full_package_path = PackagePath::new(identifier, Some(latest_version), None);
         let content = self
-            .get_directory_content_with_text(MICROSOFT, WINGET_PKGS, &full_package_path)
+            .get_directory_content_with_text(&full_package_path)
             .await?
+            .map(|file| (file.name, file.text))
             .collect::<Vec<_>>();
 
-        let version_manifest = content
-            .iter()
-            .find(|file| is_manifest_file::<VersionManifest>(&file.name, identifier, None))
-            .map(|file| serde_yaml::from_str::<VersionManifest>(&file.text))
-            .ok_or_else(|| GitHubError::ManifestNotFound {
-                r#type: ManifestType::Version,
-                path: full_package_path.clone(),
-            })??;
-
-        let locale_manifests = content
-            .iter()
-            .filter(|file| {
-                is_manifest_file::<LocaleManifest>(
-                    &file.name,
-                    identifier,
-                    Some(version_manifest.default_locale()),
-                )
-            })
-            .map(|file| serde_yaml::from_str::<LocaleManifest>(&file.text))
-            .collect::<serde_yaml::Result<_>>()?;
-
-        let default_locale_manifest = content
-            .iter()
-            .find(|file| {
-                is_manifest_file::<DefaultLocaleManifest>(
-                    &file.name,
-                    identifier,
-                    Some(version_manifest.default_locale()),
-                )
-            })
-            .map(|file| serde_yaml::from_str::<DefaultLocaleManifest>(&file.text))
-            .ok_or_else(|| GitHubError::ManifestNotFound {
-                r#type: ManifestType::DefaultLocale,
-                path: full_package_path.clone(),
-            })??;
-
-        let installer_manifest = content
-            .into_iter()
-            .find(|file| is_manifest_file::<InstallerManifest>(&file.name, identifier, None))
-            .map(|file| serde_yaml::from_str::<InstallerManifest>(&file.text))
-            .ok_or_else(|| GitHubError::ManifestNotFound {
-                r#type: ManifestType::Installer,
-                path: full_package_path.clone(),
-            })??;
-
-        Ok(Manifests {
-            installer: installer_manifest,
-            default_locale: default_locale_manifest,
-            locales: locale_manifests,
-            version: version_manifest,
-        })
+        Ok(Manifests::from_files(
+            identifier,
+            &full_package_path,
+            content,
+        )?)
     }
 
     async fn get_directory_content_with_text(
         &self,
-        owner: &str,
-        repo: &str,
         path: &PackagePath,
     ) -> Result<impl Iterator<Item = GitHubFile>, GitHubError> {
+        let upstream = Upstream::get();
         let GraphQlResponse { data, errors } = self
-            .0
-            .post(GRAPHQL_URL)
-            .run_graphql(GetDirectoryContentWithText::build(
-                GetDirectoryContentVariables::new(&owner, &repo, &format!("HEAD:{path}")),
+            .graphql(GetDirectoryContentWithText::build(
+                GetDirectoryContentVariables::new(
+                    &upstream.owner(),
+                    &upstream.repo(),
+                    &format!("{}:{path}", upstream.rev()),
+                ),
             ))
             .await?;
 
@@ -164,46 +228,79 @@ pub async fn get_manifest<T: Manifest + DeserializeOwned>(
         manifest_type: ManifestTypeWithLocale,
     ) -> Result<T, GitHubError> {
         let path = PackagePath::new(identifier, Some(version), Some(&manifest_type));
-        let content = self.get_file_content(MICROSOFT, WINGET_PKGS, &path).await?;
+        let upstream = Upstream::get();
+        let content = self
+            .get_file_content(upstream.owner(), upstream.repo(), &path)
+            .await?;
         let manifest = serde_yaml::from_str::<T>(&content)?;
         Ok(manifest)
     }
 
+    /// Gets the upstream repository, or a fork of it if an owner is given.
+    ///
+    /// If a base branch is configured for the upstream repository, it is returned in place of the
+    /// upstream repository's default branch.
     #[builder(finish_fn = send)]
     pub async fn get_winget_pkgs(
         &self,
         #[builder(into)] owner: Option<Cow<'_, str>>,
     ) -> Result<RepositoryData, GitHubError> {
-        self.get_repository_info(owner.as_deref().unwrap_or(MICROSOFT), WINGET_PKGS)
-            .await
+        let upstream = Upstream::get();
+        match owner.as_deref() {
+            Some(owner) => self.get_repository_info(owner, upstream.repo(), None).await,
+            None => {
+                self.get_repository_info(upstream.owner(), upstream.repo(), upstream.branch())
+                    .await
+            }
+        }
     }
 
     async fn get_repository_info(
         &self,
         owner: &str,
         name: &str,
+        branch: Option<&str>,
     ) -> Result<RepositoryData, GitHubError> {
+        let variables = RepositoryVariables::new(owner, name);
         let GraphQlResponse { data, errors } = self
-            .0
-            .post(GRAPHQL_URL)
-            .run_graphql(GetRepositoryInfo::build(RepositoryVariables::new(
-                owner, name,
-            )))
+            .graphql(GetRepositoryInfo::build(match branch {
+                Some(branch) => variables.with_branch(branch),
+                None => variables,
+            }))
             .await?;
 
-        let repository = data.and_then(|data| data.repository).ok_or_else(|| {
-            GitHubError::graphql_errors(
-                eyre!("failed to get {owner}/{name} when getting repository info"),
-                errors.clone(),
-            )
-        })?;
+        let repository = match data {
+            Some(GetRepositoryInfo {
+                repository: Some(repository),
+            }) => repository,
+            Some(GetRepositoryInfo { repository: None }) => {
+                return Err(GitHubError::RepositoryNonExistent {
+                    owner: owner.to_owned(),
+                    name: name.to_owned(),
+                });
+            }
+            None => {
+                return Err(GitHubError::graphql_errors(
+                    eyre!("failed to get {owner}/{name} when getting repository info"),
+                    errors,
+                ));
+            }
+        };
 
-        let default_branch = repository.default_branch_ref.ok_or_else(|| {
-            GitHubError::graphql_errors(
-                eyre!("failed to get default branch ref from {owner}/{name} when getting repository info"),
-                errors.clone(),
-            )
-        })?;
+        let default_branch = match branch {
+            Some(branch) => repository.branch_ref.ok_or_else(|| {
+                GitHubError::graphql_errors(
+                    eyre!("failed to get branch {branch} from {owner}/{name} when getting repository info"),
+                    errors.clone(),
+                )
+            })?,
+            None => repository.default_branch_ref.ok_or_else(|| {
+                GitHubError::graphql_errors(
+                    eyre!("failed to get default branch ref from {owner}/{name} when getting repository info"),
+                    errors.clone(),
+                )
+            })?,
+        };
 
         let commits = default_branch
             .target
@@ -218,6 +315,7 @@ async fn get_repository_info(
         Ok(RepositoryData {
             id: repository.id,
             owner: repository.owner.login,
+            name: repository.name,
             full_name: repository.name_with_owner,
             url: repository.url,
             default_branch_name: default_branch.name,
@@ -225,6 +323,48 @@ async fn get_repository_info(
         })
     }
 
+    /// Gets the user's fork of the upstream repository, forking it first if they do not have one.
+    ///
+    /// GitHub creates forks in the background, so this waits until the fork has its default
+    /// branch, showing what is happening on `progress` in the meantime.
+    pub async fn get_or_create_fork(
+        &self,
+        owner: &str,
+        progress: &ProgressBar,
+    ) -> Result<RepositoryData, GitHubError> {
+        const POLL_INTERVAL: Duration = Duration::from_secs(3);
+        const MAX_ATTEMPTS: u32 = 100;
+
+        match self.get_winget_pkgs().owner(owner).send().await {
+            Err(GitHubError::RepositoryNonExistent { .. }) => {}
+            result => return result,
+        }
+
+        let message = progress.message();
+        progress.set_message(format!(
+            "{owner} does not have a fork of {}. Creating one",
+            Upstream::get()
+        ));
+        let fork = self.create_fork().await?;
+
+        // GitHub may give the fork a different name, such as if the user already has a repository
+        // with the upstream's name, so wait for the repository that was actually created
+        let (fork_owner, fork_name) = fork
+            .full_name
+            .split_once('/')
+            .ok_or_else(|| GitHubError::Rest(eyre!("unexpected fork name {}", fork.full_name)))?;
+        progress.set_message(format!("Waiting for {} to be ready", fork.full_name));
+        for _ in 0..MAX_ATTEMPTS {
+            sleep(POLL_INTERVAL).await;
+            if let Ok(fork) = self.get_repository_info(fork_owner, fork_name, None).await {
+                progress.set_message(message);
+                return Ok(fork);
+            }
+        }
+
+        Err(GitHubError::ForkTimeout(fork.full_name))
+    }
+
     pub async fn create_branch(
         &self,
         fork_id: &Id,
@@ -232,9 +372,7 @@ pub async fn create_branch(
         oid: GitObjectId,
     ) -> Result<CreateBranchRef, GitHubError> {
         let GraphQlResponse { data, errors } = self
-            .0
-            .post(GRAPHQL_URL)
-            .run_graphql(CreateRef::build(
+            .graphql(CreateRef::build(
                 CreateRefVariables::builder()
                     .name(&format!("refs/heads/{branch_name}"))
                     .oid(oid)
@@ -255,20 +393,23 @@ pub async fn get_branches<T: AsRef<str>>(
     ) -> Result<(IndexMap<PullRequest, String>, Id), GitHubError> {
         let user = user.as_ref();
 
+        let upstream_name = Upstream::get().full_name();
         let mut pr_branch_map = IndexMap::new();
         let mut cursor = None;
 
         loop {
             let GraphQlResponse { data, errors } = self
-                .0
-                .post(GRAPHQL_URL)
-                .run_graphql(GetBranches::build(GetBranchesVariables {
+                .graphql(GetBranches::build(GetBranchesVariables {
                     owner: user,
-                    name: WINGET_PKGS,
+                    name: Upstream::get().repo(),
                     cursor: cursor.as_deref(),
                 }))
                 .await?;
 
+            if let Some(rate_limit) = data.as_ref().and_then(|data| data.rate_limit.as_ref()) {
+                self.rate_limits.record_cost("GetBranches", rate_limit);
+            }
+
             let repository = data.and_then(|data| data.repository).ok_or_else(|| {
                 GitHubError::graphql_errors(eyre!("failed to get fork"), errors.clone())
             })?;
@@ -304,9 +445,7 @@ pub async fn get_branches<T: AsRef<str>>(
                         MergeState::CLOSED => pull_request.state.is_closed(),
                         _ => !pull_request.state.is_open(),
                     })
-                    .find(|pull_request| {
-                        pull_request.repository.name_with_owner == WINGET_PKGS_FULL_NAME
-                    })
+                    .find(|pull_request| pull_request.repository.name_with_owner == upstream_name)
                 {
                     pr_branch_map.insert(pull_request, branch.name);
                 }
@@ -330,9 +469,7 @@ pub async fn delete_branches<I, T>(
         T: Into<String>,
     {
         let GraphQlResponse { data, errors } = self
-            .0
-            .post(GRAPHQL_URL)
-            .run_graphql(UpdateRefs::build(UpdateRefsInput::new(
+            .graphql(UpdateRefs::build(UpdateRefsInput::new(
                 RefUpdate::delete_branches(branch_names),
                 repository_id,
             )))
@@ -348,24 +485,6 @@ pub async fn delete_branches<I, T>(
         }
     }
 
-    pub fn get_all_values_from_url(
-        &self,
-        url: DecodedUrl,
-    ) -> OptionFuture<impl Future<Output = Result<GitHubValues, GitHubError>> + Sized> {
-        url.path_segments()
-            .and_then(|mut parts| {
-                let _file_name = parts.next_back()?;
-                let builder = self
-                    .get_all_values()
-                    .owner(parts.next()?.to_owned())
-                    .repo(parts.next()?.to_owned());
-                let _releases = parts.next()?;
-                let _download = parts.next()?;
-                Some(builder.tag_name(parts.join("/")).send())
-            })
-            .into()
-    }
-
     #[builder(finish_fn = send)]
     pub async fn get_all_values<'a>(
         &self,
@@ -374,9 +493,7 @@ pub async fn get_all_values<'a>(
         #[builder(into)] tag_name: Cow<'a, str>,
     ) -> Result<GitHubValues, GitHubError> {
         let GraphQlResponse { data, errors } = self
-            .0
-            .post(GRAPHQL_URL)
-            .run_graphql(GetAllValues::build(GetAllValuesVariables {
+            .graphql(GetAllValues::build(GetAllValuesVariables {
                 name: &repo,
                 owner: &owner,
                 tag_name: &tag_name,
@@ -408,7 +525,7 @@ pub async fn get_all_values<'a>(
                     ) == "license"
                 })
                 .and_then(|name| {
-                    format!("https://github.com/{owner}/{repo}/blob/HEAD/{name}")
+                    format!("https://{}/{owner}/{repo}/blob/HEAD/{name}", self.host)
                         .parse::<LicenseUrl>()
                         .ok()
                 }),
@@ -425,7 +542,7 @@ pub async fn get_all_values<'a>(
             .collect::<BTreeSet<_>>();
 
         let issues_url = if repository.has_issues_enabled {
-            format!("https://github.com/{owner}/{repo}/issues")
+            format!("https://{}/{owner}/{repo}/issues", self.host)
                 .parse::<PublisherSupportUrl>()
                 .ok()
         } else {
@@ -482,6 +599,7 @@ pub async fn remove_version(
         let deletions = self
             .get_directory_content()
             .owner(&fork.owner)
+            .repo(&fork.name)
             .branch_name(&branch_name)
             .path(&PackagePath::new(identifier, Some(version), None))
             .call()
@@ -528,10 +646,11 @@ pub async fn add_version(
         issue_resolves: &[NonZeroU32],
         created_with: Option<&str>,
         created_with_url: Option<&DecodedUrl>,
+        progress: &ProgressBar,
     ) -> Result<create_pull_request::PullRequest, GitHubError> {
         let (current_user, winget_pkgs) =
             tokio::try_join!(self.get_username(), self.get_winget_pkgs().send())?;
-        let fork = self.get_winget_pkgs().owner(&current_user).send().await?;
+        let fork = self.get_or_create_fork(&current_user, progress).await?;
         let branch_name = branch_name(identifier, version);
         let pull_request_branch = self
             .create_branch(&fork.id, &branch_name, winget_pkgs.default_branch_oid)
@@ -543,7 +662,8 @@ pub async fn add_version(
             .collect::<Vec<_>>();
         let deletions = if replace_version.is_some() {
             self.get_directory_content()
-                .owner(&current_user)
+                .owner(&fork.owner)
+                .repo(&fork.name)
                 .branch_name(&branch_name)
                 .path(&PackagePath::new(identifier, replace_version, None))
                 .call()
@@ -565,7 +685,7 @@ pub async fn add_version(
         self.create_pull_request(
             &winget_pkgs.id,
             &fork.id,
-            &format!("{current_user}:{}", pull_request_branch.name),
+            &format!("{}:{}", fork.owner, pull_request_branch.name),
             &winget_pkgs.default_branch_name,
             &commit_title,
             &pull_request_body()
@@ -576,6 +696,96 @@ pub async fn add_version(
         )
         .await
     }
+
+    /// Pushes a new commit with `changes` to the branch of an open pull request, so that it can
+    /// be fixed without losing its discussion.
+    ///
+    /// Files in the version's directory on the branch that are no longer part of `changes` are
+    /// deleted, as are the files of `replace_version` if the branch still has them.
+    #[builder(finish_fn = send)]
+    pub async fn amend_pull_request(
+        &self,
+        number: u32,
+        identifier: &PackageIdentifier,
+        version: &PackageVersion,
+        versions: Option<&BTreeSet<PackageVersion>>,
+        changes: Changes,
+        replace_version: Option<&PackageVersion>,
+    ) -> Result<get_pull_request_head::PullRequest, GitHubError> {
+        let pull_request = self.get_pull_request_head(number).await?;
+
+        if !pull_request.state.is_open() {
+            return Err(GitHubError::PullRequestNotOpen {
+                number,
+                state: pull_request.state,
+            });
+        }
+
+        if !pull_request
+            .title
+            .split_whitespace()
+            .any(|word| word.eq_ignore_ascii_case(identifier.as_str()))
+        {
+            return Err(GitHubError::PullRequestPackageMismatch {
+                number,
+                identifier: identifier.clone(),
+            });
+        }
+
+        let (Some(head_ref), Some(head_repository)) =
+            (&pull_request.head_ref, &pull_request.head_repository)
+        else {
+            return Err(GitHubError::PullRequestBranchDeleted(number));
+        };
+        let head_sha = head_ref
+            .target
+            .as_ref()
+            .map(|target| target.oid.clone())
+            .ok_or(GitHubError::PullRequestBranchDeleted(number))?;
+
+        let mut deletions = Vec::new();
+        for path in [Some(version), replace_version]
+            .into_iter()
+            .flatten()
+            .map(|version| PackagePath::new(identifier, Some(version), None))
+        {
+            let entries = match self
+                .get_directory_content()
+                .owner(&head_repository.owner.login)
+                .repo(&head_repository.name)
+                .branch_name(&head_ref.name)
+                .path(&path)
+                .call()
+                .await
+            {
+                Ok(entries) => entries,
+                // A directory that is not on the branch has nothing to delete
+                Err(GitHubError::DirectoryNonExistent { .. }) => continue,
+                Err(error) => return Err(error),
+            };
+            deletions.extend(
+                entries
+                    .filter(|entry| !changes.iter().any(|change| change.path() == entry))
+                    .map(FileDeletion::new),
+            );
+        }
+
+        let commit_title = commit_title(identifier, version, UpdateState::get(version, versions));
+        let additions = changes
+            .iter()
+            .map(|Change { path, manifest }| FileAddition::new(path, manifest))
+            .collect::<Vec<_>>();
+        self.commit()
+            .branch_id(&head_ref.id)
+            .head_sha(head_sha)
+            .message(&commit_title)
+            .additions(additions)
+            .deletions(deletions)
+            .create()
+            .await?;
+
+        Ok(pull_request)
+    }
 }
 
 pub struct GitHubValues {
@@ -612,6 +822,7 @@ pub fn new<T, S>(name: T, text: S) -> Self
 pub struct RepositoryData {
     pub id: Id,
     pub owner: String,
+    pub name: String,
     pub full_name: String,
     pub url: Url,
     pub default_branch_name: String,
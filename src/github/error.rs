@@ -1,9 +1,10 @@
 use color_eyre::{Report, eyre};
 use cynic::http::CynicReqwestError;
 use thiserror::Error;
-use winget_types::{ManifestType, PackageIdentifier};
+use winget_types::PackageIdentifier;
 
-use super::{WINGET_PKGS_FULL_NAME, utils::PackagePath};
+use super::{Upstream, graphql::types::PullRequestState, utils::PackagePath};
+use crate::manifests::ManifestsError;
 
 #[derive(Debug, Error)]
 pub enum GitHubError {
@@ -11,15 +12,32 @@ pub enum GitHubError {
     GraphQL(eyre::Error),
     #[error(transparent)]
     Rest(eyre::Error),
-    #[error("{0} does not exist in {WINGET_PKGS_FULL_NAME}")]
+    #[error("{0} does not exist in {upstream}", upstream = Upstream::get())]
     PackageNonExistent(PackageIdentifier),
-    #[error("No {type} manifest was found in {path}")]
-    ManifestNotFound {
-        r#type: ManifestType,
-        path: PackagePath,
-    },
+    #[error("{owner}/{name} does not exist")]
+    RepositoryNonExistent { owner: String, name: String },
+    #[error("Timed out waiting for the fork {0} to be ready. Please try again in a few minutes")]
+    ForkTimeout(String),
+    #[error(transparent)]
+    Manifests(#[from] ManifestsError),
+    #[error("{path} does not exist in {branch}")]
+    DirectoryNonExistent { path: PackagePath, branch: String },
     #[error("No valid files were found for {path}")]
     NoValidFiles { path: PackagePath },
+    #[error("Pull request #{0} does not exist in {upstream}", upstream = Upstream::get())]
+    PullRequestNonExistent(u32),
+    #[error("Pull request #{number} is {state} pull request and cannot be amended")]
+    PullRequestNotOpen {
+        number: u32,
+        state: PullRequestState,
+    },
+    #[error("The branch of pull request #{0} has been deleted so it cannot be amended")]
+    PullRequestBranchDeleted(u32),
+    #[error("Pull request #{number} is not for {identifier}")]
+    PullRequestPackageMismatch {
+        number: u32,
+        identifier: PackageIdentifier,
+    },
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
     #[error(transparent)]
@@ -2,7 +2,10 @@
 
 use url::Url;
 
-use super::{github_schema as schema, types::PullRequestState};
+use super::{
+    github_schema as schema,
+    types::{PullRequestState, RateLimit},
+};
 
 #[derive(cynic::QueryVariables)]
 pub struct GetBranchesVariables<'a> {
@@ -16,6 +19,7 @@ pub struct GetBranchesVariables<'a> {
 pub struct GetBranches {
     #[arguments(owner: $owner, name: $name)]
     pub repository: Option<Repository>,
+    pub rate_limit: Option<RateLimit>,
 }
 
 /// <https://docs.github.com/graphql/reference/objects#repository>
@@ -124,6 +128,12 @@ fn get_branches_query_output() {
                   }
                 }
               }
+              rateLimit {
+                cost
+                limit
+                remaining
+                resetAt
+              }
             }
         "#};
 
@@ -2,11 +2,11 @@
 
 use bon::bon;
 use color_eyre::eyre::eyre;
-use cynic::{GraphQlResponse, QueryBuilder, http::ReqwestExt};
+use cynic::{GraphQlResponse, QueryBuilder};
 
 use super::{
-    super::{GitHubError, MICROSOFT, WINGET_PKGS, client::GitHub, utils::PackagePath},
-    GRAPHQL_URL, GetFileContent, github_schema as schema,
+    super::{GitHubError, Upstream, client::GitHub, utils::PackagePath},
+    GetFileContent, github_schema as schema,
 };
 
 #[derive(cynic::QueryVariables)]
@@ -87,12 +87,10 @@ pub async fn get_file_content<O, R, P>(
         P: fmt::Display,
     {
         let GraphQlResponse { data, errors } = self
-            .0
-            .post(GRAPHQL_URL)
-            .run_graphql(GetFileContent::build(GetDirectoryContentVariables::new(
+            .graphql(GetFileContent::build(GetDirectoryContentVariables::new(
                 &owner,
                 &repo,
-                &format!("HEAD:{path}"),
+                &format!("{}:{path}", Upstream::get().rev()),
             )))
             .await?;
 
@@ -103,26 +101,29 @@ pub async fn get_file_content<O, R, P>(
     #[builder]
     pub async fn get_directory_content(
         &self,
-        #[builder(default = MICROSOFT)] owner: &str,
-        #[builder(default = WINGET_PKGS)] repo: &str,
-        #[builder(default = "HEAD")] branch_name: &str,
+        #[builder(default = Upstream::get().owner())] owner: &str,
+        #[builder(default = Upstream::get().repo())] repo: &str,
+        #[builder(default = Upstream::get().rev())] branch_name: &str,
         path: &PackagePath,
     ) -> Result<impl Iterator<Item = String>, GitHubError> {
         let GraphQlResponse { data, errors } = self
-            .0
-            .post(GRAPHQL_URL)
-            .run_graphql(GetDirectoryContent::build(
+            .graphql(GetDirectoryContent::build(
                 GetDirectoryContentVariables::new(&owner, &repo, &format!("{branch_name}:{path}")),
             ))
             .await?;
-        let entries = data
-            .and_then(|data| data.repository?.object?.into_entries())
-            .ok_or_else(|| {
-                GitHubError::graphql_errors(
-                    eyre!("failed to get {path} in {branch_name} from {owner}/{repo}"),
-                    errors,
-                )
-            })?;
+        let error = || eyre!("failed to get {path} in {branch_name} from {owner}/{repo}");
+        let Some(repository) = data.and_then(|data| data.repository) else {
+            return Err(GitHubError::graphql_errors(error(), errors));
+        };
+        let Some(object) = repository.object else {
+            return Err(GitHubError::DirectoryNonExistent {
+                path: path.clone(),
+                branch: branch_name.to_owned(),
+            });
+        };
+        let entries = object
+            .into_entries()
+            .ok_or_else(|| GitHubError::graphql_errors(error(), errors))?;
 
         Ok(entries.into_iter().filter_map(|entry| entry.path))
     }
@@ -0,0 +1,152 @@
+use std::io::Write;
+
+use color_eyre::eyre::eyre;
+use cynic::{GraphQlResponse, QueryBuilder};
+use owo_colors::OwoColorize;
+use url::Url;
+
+use super::{
+    super::{GitHubError, Upstream, client::GitHub},
+    create_ref::Ref,
+    github_schema as schema,
+    types::PullRequestState,
+};
+use crate::terminal::{Hyperlinkable, SUPPORTS_HYPERLINKS};
+
+#[derive(cynic::QueryVariables)]
+pub struct GetPullRequestHeadVariables<'a> {
+    pub owner: &'a str,
+    pub name: &'a str,
+    pub number: i32,
+}
+
+#[derive(cynic::QueryFragment)]
+#[cynic(graphql_type = "Query", variables = "GetPullRequestHeadVariables")]
+pub struct GetPullRequestHead {
+    #[arguments(owner: $owner, name: $name)]
+    pub repository: Option<Repository>,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#repository>
+#[derive(cynic::QueryFragment)]
+#[cynic(variables = "GetPullRequestHeadVariables")]
+pub struct Repository {
+    #[arguments(number: $number)]
+    pub pull_request: Option<PullRequest>,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#pullrequest>
+#[derive(cynic::QueryFragment)]
+pub struct PullRequest {
+    pub number: i32,
+    pub title: String,
+    pub url: Url,
+    pub state: PullRequestState,
+    pub head_ref: Option<Ref>,
+    pub head_repository: Option<HeadRepository>,
+}
+
+impl PullRequest {
+    pub fn print_success(&self) {
+        let mut stdout = anstream::stdout();
+
+        let _ = writeln!(
+            stdout,
+            "{} amended {}",
+            "Successfully".green(),
+            format_args!("{}#{}", Upstream::get(), self.number).hyperlink(&self.url)
+        );
+
+        // If the terminal doesn't support hyperlinks, print the pull request's URL on a new line
+        if !*SUPPORTS_HYPERLINKS {
+            let _ = writeln!(stdout, "{}", self.url);
+        }
+    }
+}
+
+/// <https://docs.github.com/graphql/reference/objects#repository>
+#[derive(cynic::QueryFragment)]
+#[cynic(graphql_type = "Repository")]
+pub struct HeadRepository {
+    pub owner: RepositoryOwner,
+    pub name: String,
+}
+
+/// <https://docs.github.com/graphql/reference/interfaces#repositoryowner>
+#[derive(cynic::QueryFragment)]
+pub struct RepositoryOwner {
+    pub login: String,
+}
+
+impl GitHub {
+    /// Returns a pull request to the upstream repository along with the branch it was opened
+    /// from.
+    pub async fn get_pull_request_head(&self, number: u32) -> Result<PullRequest, GitHubError> {
+        let upstream = Upstream::get();
+        let GraphQlResponse { data, errors } = self
+            .graphql(GetPullRequestHead::build(GetPullRequestHeadVariables {
+                owner: upstream.owner(),
+                name: upstream.repo(),
+                number: i32::try_from(number).unwrap_or(i32::MAX),
+            }))
+            .await?;
+
+        match data.and_then(|data| data.repository) {
+            Some(repository) => repository
+                .pull_request
+                .ok_or(GitHubError::PullRequestNonExistent(number)),
+            None => Err(GitHubError::graphql_errors(
+                eyre!("failed to get pull request #{number} from {upstream}"),
+                errors,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cynic::QueryBuilder;
+    use indoc::indoc;
+
+    use super::{
+        super::super::{MICROSOFT, WINGET_PKGS},
+        GetPullRequestHead, GetPullRequestHeadVariables,
+    };
+
+    #[test]
+    fn get_pull_request_head_output() {
+        const GET_PULL_REQUEST_HEAD_QUERY: &str = indoc! {r#"
+            query GetPullRequestHead($owner: String!, $name: String!, $number: Int!) {
+              repository(owner: $owner, name: $name) {
+                pullRequest(number: $number) {
+                  number
+                  title
+                  url
+                  state
+                  headRef {
+                    id
+                    name
+                    target {
+                      oid
+                    }
+                  }
+                  headRepository {
+                    owner {
+                      login
+                    }
+                    name
+                  }
+                }
+              }
+            }
+        "#};
+
+        let operation = GetPullRequestHead::build(GetPullRequestHeadVariables {
+            owner: MICROSOFT,
+            name: WINGET_PKGS,
+            number: 0,
+        });
+
+        assert_eq!(operation.query, GET_PULL_REQUEST_HEAD_QUERY);
+    }
+}
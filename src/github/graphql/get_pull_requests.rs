@@ -0,0 +1,355 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::eyre;
+use cynic::{GraphQlResponse, QueryBuilder};
+use serde::Serialize;
+use url::Url;
+
+use super::{
+    super::{GitHubError, client::GitHub},
+    get_branches::PageInfo,
+    github_schema as schema,
+    types::{PullRequestState, RateLimit},
+};
+
+#[derive(cynic::QueryVariables)]
+pub struct GetPullRequestsVariables<'a> {
+    pub query: &'a str,
+    pub cursor: Option<&'a str>,
+}
+
+#[derive(cynic::QueryFragment)]
+#[cynic(graphql_type = "Query", variables = "GetPullRequestsVariables")]
+pub struct GetPullRequests {
+    #[arguments(first: 100, type: ISSUE, query: $query, after: $cursor)]
+    pub search: SearchResultItemConnection,
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#searchresultitemconnection>
+#[derive(cynic::QueryFragment)]
+pub struct SearchResultItemConnection {
+    #[cynic(flatten)]
+    pub nodes: Vec<SearchResultItem>,
+    pub page_info: PageInfo,
+}
+
+#[derive(cynic::InlineFragments)]
+pub enum SearchResultItem {
+    PullRequest(PullRequest),
+    #[cynic(fallback)]
+    Unknown,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#pullrequest>
+#[derive(cynic::QueryFragment)]
+pub struct PullRequest {
+    pub number: i32,
+    pub title: String,
+    pub url: Url,
+    pub state: PullRequestState,
+    pub is_draft: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[arguments(first: 20)]
+    pub labels: Option<LabelConnection>,
+    #[arguments(last: 1)]
+    pub commits: PullRequestCommitConnection,
+}
+
+impl PullRequest {
+    /// Returns the names of the pull request's labels.
+    pub fn label_names(&self) -> impl Iterator<Item = &str> {
+        self.labels
+            .iter()
+            .flat_map(|labels| &labels.nodes)
+            .map(|label| label.name.as_str())
+    }
+
+    /// Returns the combined state of the checks on the pull request's latest commit, if any have
+    /// run.
+    pub fn check_state(&self) -> Option<StatusState> {
+        self.status_check_rollup().map(|rollup| rollup.state)
+    }
+
+    /// Returns the checks on the pull request's latest commit.
+    pub fn checks(&self) -> impl Iterator<Item = Check<'_>> {
+        self.status_check_rollup()
+            .into_iter()
+            .flat_map(|rollup| &rollup.contexts.nodes)
+            .filter_map(|context| match context {
+                StatusCheckRollupContext::CheckRun(CheckRun { name, conclusion }) => Some(Check {
+                    name,
+                    conclusion: conclusion.map_or("pending", CheckConclusionState::as_str),
+                }),
+                StatusCheckRollupContext::StatusContext(StatusContext { context, state }) => {
+                    Some(Check {
+                        name: context,
+                        conclusion: state.as_str(),
+                    })
+                }
+                StatusCheckRollupContext::Unknown => None,
+            })
+    }
+
+    fn status_check_rollup(&self) -> Option<&StatusCheckRollup> {
+        self.commits
+            .nodes
+            .last()
+            .and_then(|node| node.commit.status_check_rollup.as_ref())
+    }
+}
+
+/// A check run or commit status on a pull request.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Check<'a> {
+    pub name: &'a str,
+    pub conclusion: &'static str,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#labelconnection>
+#[derive(cynic::QueryFragment)]
+pub struct LabelConnection {
+    #[cynic(flatten)]
+    pub nodes: Vec<Label>,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#label>
+#[derive(cynic::QueryFragment)]
+pub struct Label {
+    pub name: String,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#pullrequestcommitconnection>
+#[derive(cynic::QueryFragment)]
+pub struct PullRequestCommitConnection {
+    #[cynic(flatten)]
+    pub nodes: Vec<PullRequestCommit>,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#pullrequestcommit>
+#[derive(cynic::QueryFragment)]
+pub struct PullRequestCommit {
+    pub commit: Commit,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#commit>
+#[derive(cynic::QueryFragment)]
+pub struct Commit {
+    pub status_check_rollup: Option<StatusCheckRollup>,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#statuscheckrollup>
+#[derive(cynic::QueryFragment)]
+pub struct StatusCheckRollup {
+    pub state: StatusState,
+    #[arguments(first: 50)]
+    pub contexts: StatusCheckRollupContextConnection,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#statuscheckrollupcontextconnection>
+#[derive(cynic::QueryFragment)]
+pub struct StatusCheckRollupContextConnection {
+    #[cynic(flatten)]
+    pub nodes: Vec<StatusCheckRollupContext>,
+}
+
+/// <https://docs.github.com/graphql/reference/unions#statuscheckrollupcontext>
+#[derive(cynic::InlineFragments)]
+pub enum StatusCheckRollupContext {
+    CheckRun(CheckRun),
+    StatusContext(StatusContext),
+    #[cynic(fallback)]
+    Unknown,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#checkrun>
+#[derive(cynic::QueryFragment)]
+pub struct CheckRun {
+    pub name: String,
+    pub conclusion: Option<CheckConclusionState>,
+}
+
+/// <https://docs.github.com/graphql/reference/objects#statuscontext>
+#[derive(cynic::QueryFragment)]
+pub struct StatusContext {
+    pub context: String,
+    pub state: StatusState,
+}
+
+/// The combined state of a commit's checks.
+///
+/// See <https://docs.github.com/graphql/reference/enums#statusstate>.
+#[derive(cynic::Enum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StatusState {
+    Error,
+    Expected,
+    Failure,
+    Pending,
+    Success,
+}
+
+impl StatusState {
+    /// Returns `true` if the checks have errored or failed.
+    #[inline]
+    pub const fn is_failure(self) -> bool {
+        matches!(self, Self::Error | Self::Failure)
+    }
+
+    /// Returns `true` if the checks are expected or still running.
+    #[inline]
+    pub const fn is_pending(self) -> bool {
+        matches!(self, Self::Expected | Self::Pending)
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Expected => "expected",
+            Self::Failure => "failure",
+            Self::Pending => "pending",
+            Self::Success => "success",
+        }
+    }
+}
+
+/// See <https://docs.github.com/graphql/reference/enums#checkconclusionstate>.
+#[derive(cynic::Enum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CheckConclusionState {
+    ActionRequired,
+    Cancelled,
+    Failure,
+    Neutral,
+    Skipped,
+    Stale,
+    StartupFailure,
+    Success,
+    TimedOut,
+}
+
+impl CheckConclusionState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ActionRequired => "action_required",
+            Self::Cancelled => "cancelled",
+            Self::Failure => "failure",
+            Self::Neutral => "neutral",
+            Self::Skipped => "skipped",
+            Self::Stale => "stale",
+            Self::StartupFailure => "startup_failure",
+            Self::Success => "success",
+            Self::TimedOut => "timed_out",
+        }
+    }
+}
+
+impl GitHub {
+    /// Returns every pull request matching a search query, such as
+    /// `repo:microsoft/winget-pkgs is:pr author:octocat`.
+    pub async fn get_pull_requests(&self, query: &str) -> Result<Vec<PullRequest>, GitHubError> {
+        let mut pull_requests = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let GraphQlResponse { data, errors } = self
+                .graphql(GetPullRequests::build(GetPullRequestsVariables {
+                    query,
+                    cursor: cursor.as_deref(),
+                }))
+                .await?;
+
+            let GetPullRequests { search, rate_limit } = data.ok_or_else(|| {
+                GitHubError::graphql_errors(eyre!("failed to search pull requests"), errors)
+            })?;
+
+            if let Some(rate_limit) = &rate_limit {
+                self.rate_limits.record_cost("GetPullRequests", rate_limit);
+            }
+
+            pull_requests.extend(search.nodes.into_iter().filter_map(|item| match item {
+                SearchResultItem::PullRequest(pull_request) => Some(pull_request),
+                SearchResultItem::Unknown => None,
+            }));
+
+            if search.page_info.has_next_page {
+                cursor = search.page_info.end_cursor;
+            } else {
+                return Ok(pull_requests);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cynic::QueryBuilder;
+    use indoc::indoc;
+
+    use super::{GetPullRequests, GetPullRequestsVariables};
+
+    #[test]
+    fn get_pull_requests_output() {
+        const GET_PULL_REQUESTS_QUERY: &str = indoc! {r#"
+            query GetPullRequests($query: String!, $cursor: String) {
+              search(first: 100, type: ISSUE, query: $query, after: $cursor) {
+                nodes {
+                  __typename
+                  ... on PullRequest {
+                    number
+                    title
+                    url
+                    state
+                    isDraft
+                    createdAt
+                    updatedAt
+                    labels(first: 20) {
+                      nodes {
+                        name
+                      }
+                    }
+                    commits(last: 1) {
+                      nodes {
+                        commit {
+                          statusCheckRollup {
+                            state
+                            contexts(first: 50) {
+                              nodes {
+                                __typename
+                                ... on CheckRun {
+                                  name
+                                  conclusion
+                                }
+                                ... on StatusContext {
+                                  context
+                                  state
+                                }
+                              }
+                            }
+                          }
+                        }
+                      }
+                    }
+                  }
+                }
+                pageInfo {
+                  endCursor
+                  hasNextPage
+                }
+              }
+              rateLimit {
+                cost
+                limit
+                remaining
+                resetAt
+              }
+            }
+        "#};
+
+        let operation = GetPullRequests::build(GetPullRequestsVariables {
+            query: "",
+            cursor: None,
+        });
+
+        assert_eq!(operation.query, GET_PULL_REQUESTS_QUERY);
+    }
+}
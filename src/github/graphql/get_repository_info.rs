@@ -6,12 +6,27 @@
 pub struct RepositoryVariables<'a> {
     pub owner: &'a str,
     pub name: &'a str,
+    pub branch: &'a str,
+    pub has_branch: bool,
 }
 
 impl<'a> RepositoryVariables<'a> {
     #[inline]
     pub const fn new(owner: &'a str, name: &'a str) -> Self {
-        RepositoryVariables { owner, name }
+        RepositoryVariables {
+            owner,
+            name,
+            branch: "",
+            has_branch: false,
+        }
+    }
+
+    /// Also requests a branch so that it can be used instead of the default branch.
+    #[inline]
+    pub const fn with_branch(mut self, branch: &'a str) -> Self {
+        self.branch = branch;
+        self.has_branch = true;
+        self
     }
 }
 
@@ -23,12 +38,18 @@ pub struct GetRepositoryInfo {
 }
 
 #[derive(cynic::QueryFragment)]
+#[cynic(variables = "RepositoryVariables")]
 pub struct Repository {
     pub id: cynic::Id,
     pub owner: RepositoryOwner,
+    pub name: String,
     pub name_with_owner: String,
     pub url: Url,
     pub default_branch_ref: Option<Ref>,
+    #[cynic(rename = "ref")]
+    #[arguments(qualifiedName: $branch)]
+    #[directives(include(if: $has_branch))]
+    pub branch_ref: Option<Ref>,
 }
 
 #[derive(cynic::QueryFragment)]
@@ -77,12 +98,13 @@ mod tests {
     #[test]
     fn get_repository_info_output() {
         const GET_REPOSITORY_INFO_QUERY: &str = indoc! {r#"
-            query GetRepositoryInfo($owner: String!, $name: String!) {
+            query GetRepositoryInfo($owner: String!, $name: String!, $branch: String!, $hasBranch: Boolean!) {
               repository(owner: $owner, name: $name) {
                 id
                 owner {
                   login
                 }
+                name
                 nameWithOwner
                 url
                 defaultBranchRef {
@@ -94,14 +116,20 @@ fn get_repository_info_output() {
                     }
                   }
                 }
+                ref(qualifiedName: $branch) @include(if: $hasBranch) {
+                  name
+                  target {
+                    __typename
+                    ... on Commit {
+                      oid
+                    }
+                  }
+                }
               }
             }
         "#};
 
-        let operation = GetRepositoryInfo::build(RepositoryVariables {
-            owner: MICROSOFT,
-            name: WINGET_PKGS,
-        });
+        let operation = GetRepositoryInfo::build(RepositoryVariables::new(MICROSOFT, WINGET_PKGS));
 
         assert_eq!(operation.query, GET_REPOSITORY_INFO_QUERY);
     }
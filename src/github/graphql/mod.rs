@@ -8,6 +8,8 @@
 pub mod get_directory_content_with_text;
 pub mod get_existing_pull_request;
 mod get_file_content;
+pub mod get_pull_request_head;
+pub mod get_pull_requests;
 pub mod get_repository_info;
 mod schema;
 pub mod types;
@@ -15,5 +17,3 @@
 
 pub use get_file_content::GetFileContent;
 pub use schema::github_schema;
-
-pub const GRAPHQL_URL: &str = "https://api.github.com/graphql";
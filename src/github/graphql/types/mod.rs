@@ -1,10 +1,12 @@
 mod base64_string;
 mod git_ref_name;
 mod pull_request;
+mod rate_limit;
 
 pub use base64_string::Base64String;
 pub use git_ref_name::GitRefName;
 pub use pull_request::{PullRequest, PullRequestState};
+pub use rate_limit::RateLimit;
 
 use super::github_schema as schema;
 
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+
+use crate::github::graphql::github_schema as schema;
+
+/// The GraphQL API's rate limit after a query, including how many points the query cost.
+///
+/// See <https://docs.github.com/graphql/reference/objects#ratelimit>.
+#[derive(cynic::QueryFragment)]
+pub struct RateLimit {
+    pub cost: i32,
+    pub limit: i32,
+    pub remaining: i32,
+    pub reset_at: DateTime<Utc>,
+}
@@ -2,13 +2,15 @@
 mod error;
 pub mod graphql;
 mod package;
+mod rate_limit;
 mod rest;
+mod upstream;
 pub mod utils;
 
-use const_format::formatcp;
 pub use error::GitHubError;
+pub use rate_limit::set_verbose;
+pub use upstream::{Repository, Upstream, UpstreamConfig};
 
 pub const MICROSOFT: &str = "microsoft";
 pub const WINGET_PKGS: &str = "winget-pkgs";
-pub const WINGET_PKGS_FULL_NAME: &str = formatcp!("{MICROSOFT}/{WINGET_PKGS}");
 pub const GITHUB_HOST: &str = "github.com";
@@ -9,6 +9,7 @@
 
 use crate::{
     commands::utils::environment::CI,
+    git::{GitError, LocalRepository},
     github::{GitHubError, client::GitHub, graphql::types::PullRequest},
     manifests::Manifests,
     prompts::text::confirm_prompt,
@@ -95,11 +96,8 @@ pub async fn into_versioned<'version, 'a>(
             .await?;
 
         Ok(Package {
-            identifier: self.identifier,
-            version,
-            manifests: self.manifests,
-            versions: self.versions,
             existing_pr,
+            ..self.with_version(version)
         })
     }
 
@@ -107,6 +105,18 @@ pub async fn into_versioned<'version, 'a>(
     pub fn latest_version(&self) -> Option<&PackageVersion> {
         self.versions.last()
     }
+
+    /// Converts the package into a versioned [Package] without checking for an existing pull
+    /// request, such as when committing to a local repository.
+    pub fn with_version(self, version: &PackageVersion) -> Package<'identifier, '_, Versioned> {
+        Package {
+            identifier: self.identifier,
+            version,
+            manifests: self.manifests,
+            versions: self.versions,
+            existing_pr: None,
+        }
+    }
 }
 
 impl GitHub {
@@ -157,3 +167,40 @@ pub async fn get_package<'identifier>(
         })
     }
 }
+
+impl LocalRepository {
+    /// Reads a versioned [Package] from the upstream revision of the local repository.
+    ///
+    /// Pull requests are not checked without GitHub, so the package never has an existing one.
+    pub fn get_versioned_package<'identifier, 'version>(
+        &self,
+        identifier: &'identifier PackageIdentifier,
+        version: &'version PackageVersion,
+    ) -> Result<Package<'identifier, 'version, Versioned>, GitError> {
+        Ok(self.get_package(identifier)?.with_version(version))
+    }
+
+    /// Reads an unversioned [Package], which may not exist, from the upstream revision of the
+    /// local repository.
+    pub fn get_package<'identifier>(
+        &self,
+        identifier: &'identifier PackageIdentifier,
+    ) -> Result<Package<'identifier, '_, Unversioned>, GitError> {
+        let versions = match self.get_versions(identifier) {
+            Ok(versions) => versions,
+            Err(GitError::PackageNonExistent(_)) => BTreeSet::default(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(Package {
+            identifier,
+            version: (),
+            manifests: versions
+                .last()
+                .map(|version| self.get_manifests(identifier, version))
+                .transpose()?,
+            versions,
+            existing_pr: None,
+        })
+    }
+}
@@ -0,0 +1,302 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use reqwest::{
+    Response, StatusCode,
+    header::{HeaderMap, RETRY_AFTER},
+};
+use tokio::time::sleep;
+use tracing::warn;
+
+use super::graphql::types::RateLimit;
+
+/// The number of times a request is retried after hitting a rate limit.
+pub const MAX_RETRIES: u32 = 3;
+
+/// How long to wait after a secondary rate limit that does not say when to retry.
+///
+/// <https://docs.github.com/rest/using-the-rest-api/rate-limits-for-the-rest-api#exceeding-the-rate-limit>
+const SECONDARY_RATE_LIMIT_DELAY: Duration = Duration::from_secs(60);
+
+/// Extra time to wait after a rate limit resets, to allow for clock skew.
+const RESET_MARGIN: Duration = Duration::from_secs(1);
+
+/// Whether to print what each GraphQL query cost and how much of each rate limit remains.
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether to print the rate limit budget as requests are made.
+///
+/// The budget is printed directly rather than logged, as debug logs are compiled out of release
+/// builds.
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+/// The rate limit that a request counts against.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Resource {
+    /// The REST API.
+    Core,
+    /// The GraphQL API, which is measured in points rather than requests.
+    GraphQl,
+}
+
+impl Resource {
+    fn from_header(value: &str) -> Option<Self> {
+        match value {
+            "core" => Some(Self::Core),
+            "graphql" => Some(Self::GraphQl),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Core => "REST",
+            Self::GraphQl => "GraphQL",
+        }
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// How much of a rate limit is left and when it resets.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Budget {
+    limit: u32,
+    remaining: u32,
+    reset: DateTime<Utc>,
+}
+
+impl Budget {
+    const LIMIT: &'static str = "x-ratelimit-limit";
+    const REMAINING: &'static str = "x-ratelimit-remaining";
+    const RESET: &'static str = "x-ratelimit-reset";
+    const RESOURCE: &'static str = "x-ratelimit-resource";
+
+    /// Reads the budget of a rate limit from the `X-RateLimit-*` headers of a response.
+    pub fn from_headers(headers: &HeaderMap) -> Option<(Resource, Self)> {
+        let header = |name| headers.get(name)?.to_str().ok();
+
+        let resource = Resource::from_header(header(Self::RESOURCE)?)?;
+        let budget = Self {
+            limit: header(Self::LIMIT)?.parse().ok()?,
+            remaining: header(Self::REMAINING)?.parse().ok()?,
+            reset: DateTime::from_timestamp(header(Self::RESET)?.parse().ok()?, 0)?,
+        };
+
+        Some((resource, budget))
+    }
+
+    /// Returns `true` if so little of the budget is left that a request could exhaust it.
+    ///
+    /// A small reserve is kept as GraphQL queries can cost more than one point.
+    pub fn is_nearly_exhausted(&self) -> bool {
+        self.remaining <= (self.limit / 100).max(1)
+    }
+
+    /// Returns how long until the budget resets.
+    pub fn time_until_reset(&self) -> Duration {
+        (self.reset - Utc::now())
+            .to_std()
+            .map_or(Duration::ZERO, |duration| duration + RESET_MARGIN)
+    }
+}
+
+impl fmt::Display for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} remaining until {}",
+            self.remaining,
+            self.limit,
+            self.reset.format("%H:%M:%S UTC")
+        )
+    }
+}
+
+/// The last known budget of each of GitHub's rate limits, shared by every clone of a client.
+#[derive(Debug, Default)]
+pub struct RateLimits(Mutex<HashMap<Resource, Budget>>);
+
+impl RateLimits {
+    /// Waits until the rate limit for `resource` resets if it is nearly exhausted.
+    pub async fn wait(&self, resource: Resource) {
+        let Some(budget) = self.get(resource).filter(Budget::is_nearly_exhausted) else {
+            return;
+        };
+
+        let delay = budget.time_until_reset();
+        if !delay.is_zero() {
+            warn!(
+                "The GitHub {resource} API rate limit is nearly exhausted ({budget}). Waiting {delay:?} for it to reset"
+            );
+            sleep(delay).await;
+        }
+
+        self.0.lock().unwrap().remove(&resource);
+    }
+
+    /// Records the budget from the headers of a response.
+    pub fn update(&self, headers: &HeaderMap) {
+        if let Some((resource, budget)) = Budget::from_headers(headers) {
+            self.insert(resource, budget);
+        }
+    }
+
+    /// Records the budget from the `rateLimit` field of a GraphQL query, logging what the query
+    /// cost.
+    pub fn record_cost(&self, operation: &str, rate_limit: &RateLimit) {
+        if VERBOSE.load(Ordering::Relaxed) {
+            eprintln!("{operation} cost {} GraphQL API points", rate_limit.cost);
+        }
+
+        self.insert(
+            Resource::GraphQl,
+            Budget {
+                limit: u32::try_from(rate_limit.limit).unwrap_or_default(),
+                remaining: u32::try_from(rate_limit.remaining).unwrap_or_default(),
+                reset: rate_limit.reset_at,
+            },
+        );
+    }
+
+    fn insert(&self, resource: Resource, budget: Budget) {
+        if VERBOSE.load(Ordering::Relaxed) {
+            eprintln!("GitHub {resource} API rate limit: {budget}");
+        }
+        self.0.lock().unwrap().insert(resource, budget);
+    }
+
+    fn get(&self, resource: Resource) -> Option<Budget> {
+        self.0.lock().unwrap().get(&resource).copied()
+    }
+}
+
+/// Returns how long to wait before retrying a request that hit a rate limit, or [`None`] if the
+/// response is not a rate limit error.
+///
+/// Secondary rate limits respond with `403 Forbidden` or `429 Too Many Requests` and usually a
+/// `Retry-After` header. Exhausting the primary rate limit responds the same way with no
+/// requests remaining.
+///
+/// <https://docs.github.com/rest/using-the-rest-api/rate-limits-for-the-rest-api#exceeding-the-rate-limit>
+pub fn retry_delay(response: &Response) -> Option<Duration> {
+    retry_delay_for(response.status(), response.headers())
+}
+
+fn retry_delay_for(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    if !matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) {
+        return None;
+    }
+
+    if let Some(seconds) = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok()?.parse().ok())
+    {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    match Budget::from_headers(headers) {
+        Some((_, budget)) if budget.remaining == 0 => Some(budget.time_until_reset()),
+        _ if status == StatusCode::TOO_MANY_REQUESTS => Some(SECONDARY_RATE_LIMIT_DELAY),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::{
+        StatusCode,
+        header::{HeaderMap, HeaderName, HeaderValue},
+    };
+    use rstest::rstest;
+
+    use super::{Budget, Resource, retry_delay_for};
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|&(name, value)| {
+                (
+                    HeaderName::from_static(name),
+                    HeaderValue::from_str(value).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn budget_from_headers() {
+        let (resource, budget) = Budget::from_headers(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "4999"),
+            ("x-ratelimit-reset", "1700000000"),
+            ("x-ratelimit-resource", "graphql"),
+        ]))
+        .unwrap();
+
+        assert_eq!(resource, Resource::GraphQl);
+        assert_eq!(budget.limit, 5000);
+        assert_eq!(budget.remaining, 4999);
+        assert_eq!(budget.reset.timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn budget_from_untracked_resource() {
+        assert!(
+            Budget::from_headers(&headers(&[
+                ("x-ratelimit-limit", "30"),
+                ("x-ratelimit-remaining", "29"),
+                ("x-ratelimit-reset", "1700000000"),
+                ("x-ratelimit-resource", "search"),
+            ]))
+            .is_none()
+        );
+    }
+
+    #[rstest]
+    #[case(5000, 4999, false)]
+    #[case(5000, 51, false)]
+    #[case(5000, 50, true)]
+    #[case(60, 1, true)]
+    #[case(60, 0, true)]
+    fn nearly_exhausted(#[case] limit: u32, #[case] remaining: u32, #[case] expected: bool) {
+        let budget = Budget {
+            limit,
+            remaining,
+            reset: chrono::Utc::now(),
+        };
+        assert_eq!(budget.is_nearly_exhausted(), expected);
+    }
+
+    #[rstest]
+    #[case::retry_after(StatusCode::FORBIDDEN, &[("retry-after", "30")], Some(Duration::from_secs(30)))]
+    #[case::too_many_requests(StatusCode::TOO_MANY_REQUESTS, &[], Some(Duration::from_secs(60)))]
+    #[case::forbidden(StatusCode::FORBIDDEN, &[], None)]
+    #[case::ok(StatusCode::OK, &[("retry-after", "30")], None)]
+    fn secondary_rate_limit_delay(
+        #[case] status: StatusCode,
+        #[case] pairs: &[(&'static str, &str)],
+        #[case] expected: Option<Duration>,
+    ) {
+        assert_eq!(retry_delay_for(status, &headers(pairs)), expected);
+    }
+}
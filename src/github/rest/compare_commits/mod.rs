@@ -4,10 +4,8 @@
 use serde::Deserialize;
 pub use status::Status;
 
-use super::{
-    GITHUB_JSON_MIME, REST_API_URL, REST_API_VERSION, X_GITHUB_API_VERSION, error::RestError,
-};
-use crate::github::{GitHubError, MICROSOFT, WINGET_PKGS, client::GitHub};
+use super::{GITHUB_JSON_MIME, REST_API_VERSION, X_GITHUB_API_VERSION, error::RestError};
+use crate::github::{GitHubError, Upstream, client::GitHub};
 
 #[derive(Copy, Clone, Deserialize)]
 pub struct CompareCommits {
@@ -58,16 +56,22 @@ pub const fn ahead_by(self) -> u32 {
 
 impl GitHub {
     pub async fn compare_upstream(&self, fork_owner: &str) -> Result<CompareCommits, GitHubError> {
+        let upstream = Upstream::get();
         let endpoint = format!(
-            "{REST_API_URL}/repos/{fork_owner}/{WINGET_PKGS}/compare/HEAD...{MICROSOFT}:HEAD"
+            "{rest_api_url}/repos/{fork_owner}/{repo}/compare/HEAD...{owner}:{rev}",
+            rest_api_url = upstream.rest_api_url(),
+            repo = upstream.repo(),
+            owner = upstream.owner(),
+            rev = upstream.rev(),
         );
 
         let response = self
-            .0
-            .get(endpoint)
-            .header(ACCEPT, GITHUB_JSON_MIME)
-            .header(X_GITHUB_API_VERSION, REST_API_VERSION)
-            .send()
+            .rest(
+                self.client
+                    .get(endpoint)
+                    .header(ACCEPT, GITHUB_JSON_MIME)
+                    .header(X_GITHUB_API_VERSION, REST_API_VERSION),
+            )
             .await?;
 
         if response.status().is_success() {
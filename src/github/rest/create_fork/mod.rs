@@ -0,0 +1,89 @@
+//! The "Create a fork" API.
+//!
+//! See <https://docs.github.com/rest/repos/forks#create-a-fork>.
+
+use reqwest::header::ACCEPT;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    super::{GitHubError, Upstream, client::GitHub},
+    GITHUB_JSON_MIME, REST_API_VERSION, X_GITHUB_API_VERSION,
+    error::RestError,
+};
+
+#[derive(Serialize)]
+struct Body {
+    /// Only copy the default branch, as pull request branches are created from the upstream's
+    /// commits anyway.
+    default_branch_only: bool,
+}
+
+#[derive(Deserialize)]
+pub struct Response {
+    /// The fork's name with its owner, such as `octocat/winget-pkgs`.
+    pub full_name: String,
+}
+
+impl GitHub {
+    /// Forks the upstream repository into the current user's account.
+    ///
+    /// GitHub creates forks asynchronously, so the fork may not be usable as soon as this returns.
+    /// If the user already has a fork, GitHub returns it instead of creating another one.
+    pub async fn create_fork(&self) -> Result<Response, GitHubError> {
+        let upstream = Upstream::get();
+        let endpoint = format!(
+            "{}/repos/{}/{}/forks",
+            upstream.rest_api_url(),
+            upstream.owner(),
+            upstream.repo()
+        );
+
+        let response = self
+            .rest(
+                self.client
+                    .post(endpoint)
+                    .header(ACCEPT, GITHUB_JSON_MIME)
+                    .header(X_GITHUB_API_VERSION, REST_API_VERSION)
+                    .json(&Body {
+                        default_branch_only: true,
+                    }),
+            )
+            .await?;
+
+        if response.status().is_success() {
+            response.json::<Response>().await.map_err(GitHubError::from)
+        } else {
+            Err(response
+                .json::<RestError>()
+                .await
+                .map_err(GitHubError::from)?
+                .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Body, Response};
+
+    #[test]
+    fn body_serializes() {
+        assert_eq!(
+            serde_json::to_string(&Body {
+                default_branch_only: true
+            })
+            .unwrap(),
+            r#"{"default_branch_only":true}"#
+        );
+    }
+
+    #[test]
+    fn response_deserializes() {
+        let response = serde_json::from_str::<Response>(
+            r#"{"id": 1296269, "name": "winget-pkgs", "full_name": "octocat/winget-pkgs"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.full_name, "octocat/winget-pkgs");
+    }
+}
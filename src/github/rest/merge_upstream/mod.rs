@@ -9,8 +9,8 @@
 use serde::{Deserialize, Serialize};
 
 use super::{
-    super::{GitHubError, WINGET_PKGS, client::GitHub},
-    GITHUB_JSON_MIME, REST_API_URL, REST_API_VERSION, X_GITHUB_API_VERSION,
+    super::{GitHubError, Upstream, client::GitHub},
+    GITHUB_JSON_MIME, REST_API_VERSION, X_GITHUB_API_VERSION,
     error::RestError,
 };
 
@@ -39,15 +39,21 @@ impl GitHub {
     ///
     /// See <https://docs.github.com/rest/branches/branches#sync-a-fork-branch-with-the-upstream-repository>.
     pub async fn sync_fork(&self, fork_owner: &str, branch: &str) -> Result<Response, GitHubError> {
-        let endpoint = format!("{REST_API_URL}/repos/{fork_owner}/{WINGET_PKGS}/merge-upstream");
+        let upstream = Upstream::get();
+        let endpoint = format!(
+            "{}/repos/{fork_owner}/{}/merge-upstream",
+            upstream.rest_api_url(),
+            upstream.repo()
+        );
 
         let response = self
-            .0
-            .post(endpoint)
-            .header(ACCEPT, GITHUB_JSON_MIME)
-            .header(X_GITHUB_API_VERSION, REST_API_VERSION)
-            .json(&Body { branch })
-            .send()
+            .rest(
+                self.client
+                    .post(endpoint)
+                    .header(ACCEPT, GITHUB_JSON_MIME)
+                    .header(X_GITHUB_API_VERSION, REST_API_VERSION)
+                    .json(&Body { branch }),
+            )
             .await?;
 
         if response.status().is_success() {
@@ -1,4 +1,5 @@
 mod compare_commits;
+mod create_fork;
 mod error;
 mod merge_upstream;
 mod tree;
@@ -9,6 +10,4 @@
 
 pub const X_GITHUB_API_VERSION: HeaderName = HeaderName::from_static("x-github-api-version");
 
-pub const REST_API_URL: &str = "https://api.github.com";
-
 pub const REST_API_VERSION: HeaderValue = HeaderValue::from_static("2026-03-10");
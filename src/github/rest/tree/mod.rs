@@ -13,8 +13,8 @@
 use winget_types::{PackageIdentifier, PackageVersion};
 
 use super::{
-    super::{GitHubError, MICROSOFT, WINGET_PKGS, client::GitHub, utils::PackagePath},
-    GITHUB_JSON_MIME, REST_API_URL, REST_API_VERSION, X_GITHUB_API_VERSION,
+    super::{GitHubError, Upstream, client::GitHub, utils::PackagePath},
+    GITHUB_JSON_MIME, REST_API_VERSION, X_GITHUB_API_VERSION,
 };
 
 /// A Git Tree which represents the hierarchy between files in a Git repository.
@@ -31,9 +31,10 @@ pub async fn get_versions(
         &self,
         package_identifier: &PackageIdentifier,
     ) -> Result<BTreeSet<PackageVersion>, GitHubError> {
+        let upstream = Upstream::get();
         self.get_all_versions(
-            MICROSOFT,
-            WINGET_PKGS,
+            upstream.owner(),
+            upstream.repo(),
             PackagePath::new(package_identifier, None, None),
         )
         .await
@@ -53,17 +54,21 @@ async fn get_all_versions(
     ) -> Result<BTreeSet<PackageVersion>, GitHubError> {
         const SEPARATOR: char = '/';
 
+        let upstream = Upstream::get();
         let endpoint = format!(
-            "{REST_API_URL}/repos/{owner}/{repo}/git/trees/HEAD:{path}?recursive={recursive}",
+            "{rest_api_url}/repos/{owner}/{repo}/git/trees/{rev}:{path}?recursive={recursive}",
+            rest_api_url = upstream.rest_api_url(),
+            rev = upstream.rev(),
             recursive = true
         );
 
         let response = self
-            .0
-            .get(endpoint)
-            .header(ACCEPT, GITHUB_JSON_MIME)
-            .header(X_GITHUB_API_VERSION, REST_API_VERSION)
-            .send()
+            .rest(
+                self.client
+                    .get(endpoint)
+                    .header(ACCEPT, GITHUB_JSON_MIME)
+                    .header(X_GITHUB_API_VERSION, REST_API_VERSION),
+            )
             .await?
             .error_for_status()?;
 
@@ -0,0 +1,220 @@
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{GITHUB_HOST, MICROSOFT, WINGET_PKGS};
+
+static UPSTREAM: OnceLock<Upstream> = OnceLock::new();
+
+/// The repository that manifests are read from and pull requests are opened against, and the
+/// GitHub instance that hosts it.
+///
+/// This defaults to `microsoft/winget-pkgs` on github.com, but can be pointed at any repository
+/// with the same layout, including one on GitHub Enterprise Server.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Upstream {
+    repository: Repository,
+    branch: Option<String>,
+    host: String,
+}
+
+impl Upstream {
+    const REV: &'static str = "HEAD";
+
+    /// Sets the upstream repository used for the rest of the process.
+    ///
+    /// This has no effect if the upstream has already been set or read.
+    pub fn init(upstream: Self) {
+        let _ = UPSTREAM.set(upstream);
+    }
+
+    /// Returns the upstream repository, which is `microsoft/winget-pkgs` unless another was set
+    /// with [`Upstream::init`].
+    pub fn get() -> &'static Self {
+        UPSTREAM.get_or_init(Self::default)
+    }
+
+    /// Returns the upstream repository with any values that are set replaced.
+    #[must_use]
+    pub fn merge(self, config: &UpstreamConfig) -> Self {
+        Self {
+            repository: config.repository.clone().unwrap_or(self.repository),
+            branch: config.branch.clone().or(self.branch),
+            host: config.host.clone().unwrap_or(self.host),
+        }
+    }
+
+    #[inline]
+    pub fn owner(&self) -> &str {
+        &self.repository.owner
+    }
+
+    #[inline]
+    pub fn repo(&self) -> &str {
+        &self.repository.repo
+    }
+
+    /// Returns the branch that pull requests are opened against, if it is not the default branch.
+    #[inline]
+    pub fn branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
+    /// Returns the revision that manifests are read from.
+    #[inline]
+    pub fn rev(&self) -> &str {
+        self.branch().unwrap_or(Self::REV)
+    }
+
+    /// Returns the host of the GitHub instance, such as `github.com`.
+    #[inline]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns `owner/repo`.
+    pub fn full_name(&self) -> String {
+        self.repository.to_string()
+    }
+
+    /// Returns the base URL of the REST API.
+    ///
+    /// GitHub Enterprise Server serves its API under `/api/v3` on the same host.
+    pub fn rest_api_url(&self) -> String {
+        if self.host == GITHUB_HOST {
+            String::from("https://api.github.com")
+        } else {
+            format!("https://{}/api/v3", self.host)
+        }
+    }
+}
+
+/// Returns the URL of the GraphQL API of the GitHub instance at `host`.
+pub fn graphql_url(host: &str) -> String {
+    if host == GITHUB_HOST {
+        String::from("https://api.github.com/graphql")
+    } else {
+        format!("https://{host}/api/graphql")
+    }
+}
+
+impl Default for Upstream {
+    fn default() -> Self {
+        Self {
+            repository: Repository {
+                owner: MICROSOFT.to_owned(),
+                repo: WINGET_PKGS.to_owned(),
+            },
+            branch: None,
+            host: GITHUB_HOST.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Upstream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.repository.fmt(f)
+    }
+}
+
+/// The `upstream` section of the configuration file.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct UpstreamConfig {
+    /// The repository as `owner/repo`.
+    pub repository: Option<Repository>,
+
+    /// The branch to open pull requests against instead of the default branch.
+    pub branch: Option<String>,
+
+    /// The host of a GitHub Enterprise Server instance.
+    pub host: Option<String>,
+}
+
+/// A GitHub repository, written as `owner/repo`.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Repository {
+    owner: String,
+    repo: String,
+}
+
+impl fmt::Display for Repository {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.repo)
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+#[error(r#"Expected a repository as OWNER/REPO but got "{0}""#)]
+pub struct RepositoryError(String);
+
+impl FromStr for Repository {
+    type Err = RepositoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() && !repo.contains('/') => {
+                Ok(Self {
+                    owner: owner.to_owned(),
+                    repo: repo.to_owned(),
+                })
+            }
+            _ => Err(RepositoryError(s.to_owned())),
+        }
+    }
+}
+
+impl TryFrom<String> for Repository {
+    type Error = RepositoryError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{Repository, Upstream, UpstreamConfig, graphql_url};
+
+    #[rstest]
+    #[case("contoso/winget-pkgs", true)]
+    #[case("contoso", false)]
+    #[case("/winget-pkgs", false)]
+    #[case("contoso/", false)]
+    #[case("contoso/winget/pkgs", false)]
+    fn parse_repository(#[case] repository: &str, #[case] is_valid: bool) {
+        assert_eq!(repository.parse::<Repository>().is_ok(), is_valid);
+    }
+
+    #[test]
+    fn default() {
+        let upstream = Upstream::default();
+        assert_eq!(upstream.full_name(), "microsoft/winget-pkgs");
+        assert_eq!(upstream.rev(), "HEAD");
+        assert_eq!(upstream.rest_api_url(), "https://api.github.com");
+        assert_eq!(
+            graphql_url(upstream.host()),
+            "https://api.github.com/graphql"
+        );
+    }
+
+    #[test]
+    fn enterprise_server() {
+        let upstream = Upstream::default().merge(&UpstreamConfig {
+            repository: Some("contoso/winget-pkgs".parse().unwrap()),
+            branch: Some("main".to_owned()),
+            host: Some("github.contoso.com".to_owned()),
+        });
+        assert_eq!(upstream.owner(), "contoso");
+        assert_eq!(upstream.rev(), "main");
+        assert_eq!(upstream.rest_api_url(), "https://github.contoso.com/api/v3");
+        assert_eq!(
+            graphql_url(upstream.host()),
+            "https://github.contoso.com/api/graphql"
+        );
+    }
+}
@@ -3,8 +3,12 @@
 
 mod analysis;
 mod commands;
+mod config;
+mod dirs;
 mod download;
 mod editor;
+mod forge;
+mod git;
 mod github;
 mod manifests;
 mod prompts;
@@ -16,7 +20,8 @@
 
 use clap::{Parser, crate_name};
 use color_eyre::eyre::Result;
-use commands::Commands;
+use commands::{Commands, utils::UpstreamArgs};
+use config::Config;
 use token::TokenManager;
 use tracing::{Level, metadata::LevelFilter};
 use tracing_indicatif::IndicatifLayer;
@@ -28,9 +33,17 @@ async fn main() -> Result<()> {
         .display_env_section(false)
         .install()?;
 
+    let cli = Cli::parse();
     setup_logging();
+    github::set_verbose(cli.verbose);
 
-    Cli::parse().command.run().await?;
+    let config = if cli.command.uses_config() {
+        Config::load()?
+    } else {
+        Config::default()
+    };
+    cli.upstream.init(&config.upstream);
+    cli.command.run(&config).await?;
 
     TokenManager::unset_default_store();
 
@@ -61,6 +74,11 @@ fn setup_logging() {
 struct Cli {
     #[arg(short = 'v', short_alias = 'V', long, action = clap::builder::ArgAction::Version)]
     version: (),
+    /// Show how much of the GitHub API rate limit remains after each request
+    #[arg(long, global = true, env = "KOMAC_VERBOSE")]
+    verbose: bool,
+    #[command(flatten)]
+    upstream: UpstreamArgs,
     #[command(subcommand)]
     command: Commands,
 }
@@ -7,10 +7,11 @@
 
 use anstream::AutoStream;
 use owo_colors::{OwoColorize, Style, colors::css::SlateGrey};
+use thiserror::Error;
 use tree_sitter_highlight::{Highlight, HighlightConfiguration, HighlightEvent, Highlighter};
 pub use url::Url;
 use winget_types::{
-    Manifest, PackageIdentifier, PackageVersion, VersionManifest,
+    Manifest, ManifestType, PackageIdentifier, PackageVersion, VersionManifest,
     installer::InstallerManifest,
     locale::{DefaultLocaleManifest, LocaleManifest},
     url::ReleaseNotesUrl,
@@ -20,7 +21,7 @@
     github::{
         client::GitHubValues,
         utils::{
-            PackagePath,
+            PackagePath, is_manifest_file,
             pull_request::{Change, Changes},
         },
     },
@@ -54,6 +55,66 @@ pub fn package_version(&self) -> &PackageVersion {
         self.version.package_version()
     }
 
+    /// Reads the manifests of a package version from the files in its directory, given as pairs
+    /// of file names and contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required manifest is missing or a manifest cannot be deserialized.
+    pub fn from_files(
+        identifier: &PackageIdentifier,
+        path: &PackagePath,
+        files: Vec<(String, String)>,
+    ) -> Result<Self, ManifestsError> {
+        let not_found = |r#type| ManifestsError::NotFound {
+            r#type,
+            path: path.clone(),
+        };
+
+        let version_manifest = files
+            .iter()
+            .find(|(name, _)| is_manifest_file::<VersionManifest>(name, identifier, None))
+            .map(|(_, text)| serde_yaml::from_str::<VersionManifest>(text))
+            .ok_or_else(|| not_found(ManifestType::Version))??;
+
+        let locale_manifests = files
+            .iter()
+            .filter(|(name, _)| {
+                is_manifest_file::<LocaleManifest>(
+                    name,
+                    identifier,
+                    Some(version_manifest.default_locale()),
+                )
+            })
+            .map(|(_, text)| serde_yaml::from_str::<LocaleManifest>(text))
+            .collect::<serde_yaml::Result<_>>()?;
+
+        let default_locale_manifest = files
+            .iter()
+            .find(|(name, _)| {
+                is_manifest_file::<DefaultLocaleManifest>(
+                    name,
+                    identifier,
+                    Some(version_manifest.default_locale()),
+                )
+            })
+            .map(|(_, text)| serde_yaml::from_str::<DefaultLocaleManifest>(text))
+            .ok_or_else(|| not_found(ManifestType::DefaultLocale))??;
+
+        let installer_manifest = files
+            .into_iter()
+            .find(|(name, _)| is_manifest_file::<InstallerManifest>(name, identifier, None))
+            .map(|(_, text)| serde_yaml::from_str::<InstallerManifest>(&text))
+            .ok_or_else(|| not_found(ManifestType::Installer))??;
+
+        Ok(Self {
+            installer: installer_manifest,
+            default_locale: default_locale_manifest,
+            locales: locale_manifests,
+            version: version_manifest,
+        })
+    }
+
     pub fn create(
         &self,
         identifier: &PackageIdentifier,
@@ -115,6 +176,17 @@ pub fn update(
     }
 }
 
+#[derive(Debug, Error)]
+pub enum ManifestsError {
+    #[error("No {type} manifest was found in {path}")]
+    NotFound {
+        r#type: ManifestType,
+        path: PackagePath,
+    },
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+}
+
 impl Display for Manifests {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
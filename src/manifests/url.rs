@@ -1,22 +1,104 @@
 use std::{
-    fmt,
+    collections::BTreeSet,
+    fmt, mem,
     ops::{Deref, DerefMut},
     str::FromStr,
 };
 
+use camino::Utf8PathBuf;
+use thiserror::Error;
 use url::ParseError;
-use winget_types::{installer::Architecture, url::DecodedUrl};
+use winget_types::{
+    LanguageTag,
+    installer::{
+        Architecture, Installer, InstallerType, NestedInstallerFiles, Scope, Switches,
+        switches::CustomSwitch,
+    },
+    url::DecodedUrl,
+};
 
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+/// An installer URL, optionally followed by `|`-separated overrides for the values detected from
+/// the installer.
+///
+/// For example, `https://example.com/setup.exe|x64|scope=user|type=portable`. A bare value is
+/// treated as an architecture.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Url {
     inner: DecodedUrl,
-    override_architecture: Option<Architecture>,
+    overrides: Overrides,
+}
+
+/// Values that replace those detected from an installer.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Overrides {
+    pub architecture: Option<Architecture>,
+    pub scope: Option<Scope>,
+    pub installer_type: Option<InstallerType>,
+    pub nested_installer: Option<Utf8PathBuf>,
+    pub locale: Option<LanguageTag>,
+    pub custom_switch: Option<CustomSwitch>,
+}
+
+impl Overrides {
+    const ARCHITECTURE: &'static str = "arch";
+    const SCOPE: &'static str = "scope";
+    const TYPE: &'static str = "type";
+    const NESTED: &'static str = "nested";
+    const LOCALE: &'static str = "locale";
+    const CUSTOM: &'static str = "custom";
+
+    /// Applies the overrides to an installer.
+    ///
+    /// The architecture is not applied here as it falls back to the architecture detected from the
+    /// URL itself. The custom switch is added to any custom switch detected from the installer.
+    pub fn apply(&self, installer: &mut Installer) {
+        if let Some(scope) = self.scope {
+            installer.scope = Some(scope);
+        }
+        if let Some(installer_type) = self.installer_type {
+            installer.r#type = Some(installer_type);
+        }
+        if let Some(nested_installer) = &self.nested_installer {
+            installer.nested_installer_files = BTreeSet::from([NestedInstallerFiles {
+                relative_file_path: nested_installer.clone(),
+                portable_command_alias: None,
+            }]);
+        }
+        if let Some(locale) = &self.locale {
+            installer.locale = Some(locale.clone());
+        }
+        if let Some(custom_switch) = &self.custom_switch {
+            // Merging keeps the switches that have no getter to rebuild them from, such as the
+            // install location
+            *installer = mem::take(installer).merge_with(Installer {
+                switches: Switches::builder().custom(custom_switch.clone()).build(),
+                ..Installer::default()
+            });
+        }
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum UrlError {
+    #[error(transparent)]
+    Url(#[from] ParseError),
+    #[error(
+        r#"Unknown override "{0}". Expected an architecture or one of arch=, scope=, type=, nested=, locale= or custom="#
+    )]
+    UnknownOverride(String),
+    #[error(r#"Invalid value for the {key} override: "{value}""#)]
+    InvalidOverride { key: String, value: String },
 }
 
 impl Url {
     #[inline]
     pub const fn override_architecture(&self) -> Option<Architecture> {
-        self.override_architecture
+        self.overrides.architecture
+    }
+
+    #[inline]
+    pub const fn overrides(&self) -> &Overrides {
+        &self.overrides
     }
 
     #[inline]
@@ -59,14 +141,54 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 impl FromStr for Url {
-    type Err = ParseError;
+    type Err = UrlError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (url, architecture) = s.rsplit_once('|').unwrap_or((s, ""));
+        let mut parts = s.split('|');
+        let url = parts.next().unwrap_or_default();
+
+        let mut overrides = Overrides::default();
+        for part in parts.map(str::trim).filter(|part| !part.is_empty()) {
+            let invalid = |key: &str, value: &str| UrlError::InvalidOverride {
+                key: key.to_owned(),
+                value: value.to_owned(),
+            };
+
+            let Some((key, value)) = part.split_once('=') else {
+                overrides.architecture = Some(
+                    part.parse()
+                        .map_err(|_| UrlError::UnknownOverride(part.to_owned()))?,
+                );
+                continue;
+            };
+
+            match key.trim().to_ascii_lowercase().as_str() {
+                Overrides::ARCHITECTURE | "architecture" => {
+                    overrides.architecture = Some(value.parse().map_err(|_| invalid(key, value))?);
+                }
+                Overrides::SCOPE => {
+                    overrides.scope = Some(value.parse().map_err(|_| invalid(key, value))?);
+                }
+                Overrides::TYPE => {
+                    overrides.installer_type =
+                        Some(value.parse().map_err(|_| invalid(key, value))?);
+                }
+                Overrides::NESTED => {
+                    overrides.nested_installer = Some(Utf8PathBuf::from(value));
+                }
+                Overrides::LOCALE => {
+                    overrides.locale = Some(value.parse().map_err(|_| invalid(key, value))?);
+                }
+                Overrides::CUSTOM => {
+                    overrides.custom_switch = Some(value.parse().map_err(|_| invalid(key, value))?);
+                }
+                _ => return Err(UrlError::UnknownOverride(part.to_owned())),
+            }
+        }
 
         Ok(Self {
             inner: url.parse()?,
-            override_architecture: architecture.parse().ok(),
+            overrides,
         })
     }
 }
@@ -75,7 +197,104 @@ impl From<DecodedUrl> for Url {
     fn from(url: DecodedUrl) -> Self {
         Self {
             inner: url,
-            override_architecture: None,
+            overrides: Overrides::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use rstest::rstest;
+    use winget_types::installer::{
+        Architecture, Installer, InstallerType, Scope, switches::Switches,
+    };
+
+    use super::{Url, UrlError};
+
+    #[test]
+    fn no_overrides() {
+        let url = "https://example.com/setup.exe".parse::<Url>().unwrap();
+        assert_eq!(url.as_str(), "https://example.com/setup.exe");
+        assert_eq!(url.overrides(), &super::Overrides::default());
+    }
+
+    #[rstest]
+    #[case::bare("https://example.com/setup.exe|x64")]
+    #[case::key("https://example.com/setup.exe|arch=x64")]
+    fn architecture(#[case] url: &str) {
+        let url = url.parse::<Url>().unwrap();
+        assert_eq!(url.as_str(), "https://example.com/setup.exe");
+        assert_eq!(url.override_architecture(), Some(Architecture::X64));
+    }
+
+    #[test]
+    fn all_overrides() {
+        let url = "https://example.com/tool.zip|arm64|scope=user|type=portable|nested=bin/tool.exe|locale=en-US|custom=/norestart"
+            .parse::<Url>()
+            .unwrap();
+
+        let overrides = url.overrides();
+        assert_eq!(overrides.architecture, Some(Architecture::Arm64));
+        assert_eq!(overrides.scope, Some(Scope::User));
+        assert_eq!(overrides.installer_type, Some(InstallerType::Portable));
+        assert_eq!(
+            overrides.nested_installer,
+            Some(Utf8PathBuf::from("bin/tool.exe"))
+        );
+        assert_eq!(overrides.locale.as_ref().unwrap().to_string(), "en-US");
+        assert_eq!(
+            overrides.custom_switch.as_ref().unwrap().to_string(),
+            "/norestart"
+        );
+    }
+
+    #[test]
+    fn apply_keeps_other_switches() {
+        let mut installer = Installer {
+            switches: Switches::builder()
+                .silent("/S".parse().unwrap())
+                .install_location(r#"/D="<INSTALLPATH>""#.parse().unwrap())
+                .custom("/old".parse().unwrap())
+                .build(),
+            ..Installer::default()
+        };
+
+        "https://example.com/setup.exe|scope=machine|custom=/norestart"
+            .parse::<Url>()
+            .unwrap()
+            .overrides()
+            .apply(&mut installer);
+
+        assert_eq!(installer.scope, Some(Scope::Machine));
+        assert_eq!(
+            installer.switches,
+            Switches::builder()
+                .silent("/S".parse().unwrap())
+                .install_location(r#"/D="<INSTALLPATH>""#.parse().unwrap())
+                .custom("/old /norestart".parse().unwrap())
+                .build()
+        );
+    }
+
+    #[rstest]
+    #[case::unknown_key("https://example.com/setup.exe|foo=bar")]
+    #[case::unknown_architecture("https://example.com/setup.exe|x65")]
+    fn unknown_override(#[case] url: &str) {
+        assert!(matches!(
+            url.parse::<Url>(),
+            Err(UrlError::UnknownOverride(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_override() {
+        assert_eq!(
+            "https://example.com/setup.exe|scope=everyone".parse::<Url>(),
+            Err(UrlError::InvalidOverride {
+                key: "scope".to_owned(),
+                value: "everyone".to_owned(),
+            })
+        );
+    }
+}
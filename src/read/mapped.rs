@@ -0,0 +1,119 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor, Read, Seek, SeekFrom},
+};
+
+use memmap2::Mmap;
+use tracing::debug;
+
+/// A file opened for analysis.
+///
+/// Where possible, the file is memory-mapped so that the many small reads and seeks made by the
+/// parsers are copies out of the page cache rather than system calls. If the file cannot be
+/// mapped, such as when it is empty, it is read through a buffer instead.
+pub enum MappedReader {
+    Mapped(Cursor<Mmap>),
+    Buffered(BufReader<File>),
+}
+
+impl MappedReader {
+    /// Maps a file into memory, falling back to buffered reads if it cannot be mapped.
+    pub fn new(file: File) -> Self {
+        // SAFETY: the file must not be modified while it is mapped. Komac only maps files it has
+        // downloaded or that were given to it to analyze, and does not write to either.
+        match unsafe { Mmap::map(&file) } {
+            Ok(mmap) if !mmap.is_empty() => Self::Mapped(Cursor::new(mmap)),
+            Ok(_) => Self::Buffered(BufReader::new(file)),
+            Err(error) => {
+                debug!(%error, "Falling back to buffered reads");
+                Self::Buffered(BufReader::new(file))
+            }
+        }
+    }
+}
+
+impl Read for MappedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Mapped(cursor) => cursor.read(buf),
+            Self::Buffered(reader) => reader.read(buf),
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        match self {
+            Self::Mapped(cursor) => cursor.read_exact(buf),
+            Self::Buffered(reader) => reader.read_exact(buf),
+        }
+    }
+}
+
+impl BufRead for MappedReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self {
+            Self::Mapped(cursor) => cursor.fill_buf(),
+            Self::Buffered(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        match self {
+            Self::Mapped(cursor) => cursor.consume(amount),
+            Self::Buffered(reader) => reader.consume(amount),
+        }
+    }
+}
+
+impl Seek for MappedReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Self::Mapped(cursor) => cursor.seek(pos),
+            Self::Buffered(reader) => reader.seek(pos),
+        }
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        match self {
+            Self::Mapped(cursor) => cursor.stream_position(),
+            Self::Buffered(reader) => reader.stream_position(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    use tempfile::tempfile;
+
+    use super::MappedReader;
+
+    #[test]
+    fn read_and_seek() {
+        let mut file = tempfile().unwrap();
+        file.write_all(b"0123456789").unwrap();
+
+        let mut reader = MappedReader::new(file);
+        assert!(matches!(reader, MappedReader::Mapped(_)));
+
+        reader.seek(SeekFrom::Start(4)).unwrap();
+        let mut buf = [0; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"456");
+
+        reader.seek(SeekFrom::End(-2)).unwrap();
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"89");
+    }
+
+    #[test]
+    fn empty_file_is_buffered() {
+        let mut reader = MappedReader::new(tempfile().unwrap());
+        assert!(matches!(reader, MappedReader::Buffered(_)));
+
+        let mut contents = Vec::new();
+        reader.read_to_end(&mut contents).unwrap();
+        assert!(contents.is_empty());
+    }
+}
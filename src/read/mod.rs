@@ -1,3 +1,5 @@
 mod ext;
+mod mapped;
 
 pub use ext::ReadBytesExt;
+pub use mapped::MappedReader;
@@ -12,9 +12,7 @@
 use thiserror::Error;
 use tokio::runtime::Handle;
 
-use crate::{commands::utils::environment::CI, prompts::handle_inquire_error};
-
-const GITHUB_API_ENDPOINT: &str = "https://api.github.com/octocat";
+use crate::{commands::utils::environment::CI, github::Upstream, prompts::handle_inquire_error};
 
 static DEFAULT_STORE_SET: AtomicBool = AtomicBool::new(false);
 
@@ -126,7 +124,7 @@ pub fn prompt(
 
     pub async fn validate(client: &Client, token: &str) -> Result<(), TokenError> {
         match client
-            .get(GITHUB_API_ENDPOINT)
+            .get(format!("{}/octocat", Upstream::get().rest_api_url()))
             .bearer_auth(token)
             .send()
             .await